@@ -42,13 +42,16 @@ __ghr_complete() {
   cword="${COMP_WORDS[COMP_CWORD]}"
 
   if [ "${COMP_CWORD}" = 1 ]; then
-    COMPREPLY=($(__ghr_complete__static "${cword}" --help add browse cd clone delete help init list open path profile shell sync version))
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help add archive browse cd clone config delete exec fetch gc help import init list migrate migrate-layout open path profile reindex relocate rename search shell status sync trash unarchive verify version))
     return 0
   fi
 
   case "${COMP_WORDS[1]}" in
   add)
-    COMPREPLY=($(__ghr_complete__static "${cword}" --help))
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --copy --symlink --remote -f --force))
+    ;;
+  archive)
+    COMPREPLY=($(__ghr_complete__repos "${cword}"))
     ;;
   browse)
     COMPREPLY=($(__ghr_complete__repos "${cword}"))
@@ -59,14 +62,35 @@ __ghr_complete() {
   clone)
     COMPREPLY=($(__ghr_complete__static "${cword}" --help))
     ;;
+  config)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help list subscribe unsubscribe update))
+    ;;
   delete)
     COMPREPLY=($(__ghr_complete__repos "${cword}"))
     ;;
-  init)
+  exec)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help))
+    ;;
+  fetch)
     COMPREPLY=($(__ghr_complete__static "${cword}" --help))
     ;;
+  gc)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --dry-run -f --force))
+    ;;
+  import)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --dry-run -f --force))
+    ;;
+  init)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --cd --open --template))
+    ;;
   list)
-    COMPREPLY=($(__ghr_complete__static "${cword}" --help --no-host --no-owner -p --path))
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --no-host --no-owner -p --path -l --long -r --recent))
+    ;;
+  migrate)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --from --root --symlink --dry-run -f --force ghq gopath))
+    ;;
+  migrate-layout)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --from --dry-run -f --force))
     ;;
   open)
     if [ "${COMP_CWORD}" = 2 ]; then
@@ -81,25 +105,52 @@ __ghr_complete() {
     ;;
   profile)
     if [ "$COMP_CWORD" = 2 ]; then
-      COMPREPLY=($(__ghr_complete__static "${cword}" --help list show apply))
+      COMPREPLY=($(__ghr_complete__static "${cword}" --help list show apply unapply diff sync))
     else
       case "${COMP_WORDS[2]}" in
       show|apply)
         COMPREPLY=($(__ghr_complete__profiles "${cword}" --help))
         ;;
+      diff)
+        COMPREPLY=($(__ghr_complete__repos "${cword}"))
+        ;;
+      sync)
+        COMPREPLY=($(__ghr_complete__static "${cword}" --help --dry-run))
+        ;;
       *)
         ;;
       esac
     fi
     ;;
+  reindex)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --root))
+    ;;
+  relocate)
+    COMPREPLY=($(__ghr_complete__repos "${cword}" --help --all --dry-run))
+    ;;
+  rename)
+    COMPREPLY=($(__ghr_complete__repos "${cword}"))
+    ;;
   search)
     COMPREPLY=($(__ghr_complete__static "${cword}" --help))
     ;;
   shell)
     COMPREPLY=($(__ghr_complete__static "${cword}" --help))
     ;;
+  status)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help))
+    ;;
   sync)
-    COMPREPLY=($(__ghr_complete__static "${cword}" --help dump restore))
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help dump import restore))
+    ;;
+  trash)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help empty list restore))
+    ;;
+  unarchive)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help))
+    ;;
+  verify)
+    COMPREPLY=($(__ghr_complete__static "${cword}" --help --fix))
     ;;
   version)
     COMPREPLY=($(__ghr_complete__static "${cword}" --help))
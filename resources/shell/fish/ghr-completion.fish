@@ -16,25 +16,40 @@ complete -c ghr -f
 
 # Complete commands with their description
 complete -c ghr -n "__fish_is_arg_n 1" -a add -d "Add an existing repository into the ghr managed directory"
+complete -c ghr -n "__fish_is_arg_n 1" -a archive -d "Packs repositories into archives under the root, removing their working directories"
 complete -c ghr -n "__fish_is_arg_n 1" -a browse -d "Browse a repository on web"
 complete -c ghr -n "__fish_is_arg_n 1" -a cd -d "Change directory to one of the managed repositories (Shell extension required)"
 complete -c ghr -n "__fish_is_arg_n 1" -a clone -d "Clones a Git repository to local"
+complete -c ghr -n "__fish_is_arg_n 1" -a config -d "Manages configuration repositories shared in your team"
 complete -c ghr -n "__fish_is_arg_n 1" -a delete -d "Deletes a repository from local"
+complete -c ghr -n "__fish_is_arg_n 1" -a exec -d "Runs a command in each of the selected repositories"
+complete -c ghr -n "__fish_is_arg_n 1" -a fetch -d "Fetches all remotes of the selected repositories"
+complete -c ghr -n "__fish_is_arg_n 1" -a gc -d "Removes empty directories and moves non-repository directories in the roots into the trash"
+complete -c ghr -n "__fish_is_arg_n 1" -a import -d "Moves all repositories found in a directory into the ghr managed directory"
 complete -c ghr -n "__fish_is_arg_n 1" -a init -d "Initialises a Git repository in local"
 complete -c ghr -n "__fish_is_arg_n 1" -a list -d "Lists all managed repositories"
+complete -c ghr -n "__fish_is_arg_n 1" -a migrate -d "Moves repositories managed by ghq or placed in GOPATH into the ghr managed directory"
+complete -c ghr -n "__fish_is_arg_n 1" -a migrate-layout -d "Moves repositories into the layout configured currently"
 complete -c ghr -n "__fish_is_arg_n 1" -a open -d "Opens a repository in an application"
 complete -c ghr -n "__fish_is_arg_n 1" -a path -d "Prints the path to root, owner, or a repository"
 complete -c ghr -n "__fish_is_arg_n 1" -a profile -d "Manages profiles to use in repositories"
+complete -c ghr -n "__fish_is_arg_n 1" -a reindex -d "Rebuilds the index of repositories to speed up listing"
+complete -c ghr -n "__fish_is_arg_n 1" -a relocate -d "Moves repositories renamed or transferred on the platform to their new location"
+complete -c ghr -n "__fish_is_arg_n 1" -a rename -d "Renames a repository on the platform, then relocates the local checkout"
 complete -c ghr -n "__fish_is_arg_n 1" -a search -d "Perform a fuzzy search on the repositories list"
 complete -c ghr -n "__fish_is_arg_n 1" -a shell -d "Writes a shell script to extend ghr features"
+complete -c ghr -n "__fish_is_arg_n 1" -a status -d "Prints the branch and state of the selected repositories"
 complete -c ghr -n "__fish_is_arg_n 1" -a sync -d "Sync repositories between your devices"
+complete -c ghr -n "__fish_is_arg_n 1" -a trash -d "Lists, restores or empties the repositories deleted into the trash"
+complete -c ghr -n "__fish_is_arg_n 1" -a unarchive -d "Restores archived repositories into their original location"
+complete -c ghr -n "__fish_is_arg_n 1" -a verify -d "Checks that repositories are placed at the location of their remotes"
 complete -c ghr -n "__fish_is_arg_n 1" -a version -d "Prints the version of this application"
 
-# Complete the 2nd argument of add command using the file path
-complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from add" -f
+# Complete the 2nd argument of add and import commands using the file path
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from add import" -f
 
-# Complete the 2nd argument of cd, delete, path, open, and browse commands using the repository list
-complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from browse cd delete path open" -a "(__ghr_complete_repos)"
+# Complete the 2nd argument of archive, cd, delete, path, open, browse, relocate and rename commands using the repository list
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from archive browse cd delete path open relocate rename" -a "(__ghr_complete_repos)"
 
 # Complete the 3rd argument of open command using the known command list
 complete -c ghr -n "__fish_is_arg_n 3; and __fish_seen_subcommand_from open" -a "(complete -C '')"
@@ -43,10 +58,28 @@ complete -c ghr -n "__fish_is_arg_n 3; and __fish_seen_subcommand_from open" -a
 complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from profile" -a list -d "Lists all configured profiles"
 complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from profile" -a show -d "Shows a profile in TOML format"
 complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from profile" -a apply -d "Apply a profile"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from profile" -a unapply -d "Reverts the values written by the profile applied previously"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from profile" -a diff -d "Shows differences between a repository and its profile"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from profile" -a sync -d "Applies the profiles resolved by the rules to all repositories drifted from them"
 
 # Complete the 3rd argument of profile list subcommand using the profile list
 complete -c ghr -n "__fish_is_arg_n 3; and __fish_seen_subcommand_from profile; and __fish_seen_subcommand_from show apply" -a "(ghr profile list --short)"
 
+# Complete the 3rd argument of profile diff subcommand using the repository list
+complete -c ghr -n "__fish_is_arg_n 3; and __fish_seen_subcommand_from profile; and __fish_seen_subcommand_from diff" -a "(__ghr_complete_repos)"
+
+# Complete subcommands of config command with their description
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from config" -a list -d "Lists all subscribed configuration repositories"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from config" -a subscribe -d "Subscribes a configuration repository shared in your team"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from config" -a unsubscribe -d "Unsubscribes a configuration repository"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from config" -a update -d "Pulls the latest versions of the subscribed configuration repositories"
+
 # Complete subcommands of sync command with their description
 complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from sync" -a dump -d "Dump remotes and the current ref of all repositories"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from sync" -a import -d "Clone repositories from a manifest of vcstool, repo or mr"
 complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from sync" -a restore -d "Restore repositories from the dumped file"
+
+# Complete subcommands of trash command with their description
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from trash" -a list -d "Lists the deleted repositories in the trash"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from trash" -a restore -d "Restores a deleted repository to its original location"
+complete -c ghr -n "__fish_is_arg_n 2; and __fish_seen_subcommand_from trash" -a empty -d "Deletes the repositories in the trash permanently"
@@ -0,0 +1,168 @@
+use std::fs::{File, create_dir_all, remove_dir_all, remove_file, rename};
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow, bail};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use tar::{Archive, Builder};
+use walkdir::WalkDir;
+
+use crate::index::Index;
+use crate::path::Path;
+use crate::root::{Root, Roots, prune_empty};
+
+const ARCHIVE_DIR_NAME: &str = "archive";
+const EXTENSION: &str = "tar.gz";
+
+fn archive_path(root: &Root) -> PathBuf {
+    root.data_path().join(ARCHIVE_DIR_NAME)
+}
+
+/// A repository packed into a compressed tarball under its root, as
+/// `archive/<host>/<owner>/<repo>.tar.gz`. The whole checkout is packed including `.git`,
+/// so remotes, branches, stashes and untracked files are kept as they are.
+pub struct Archived<'a> {
+    pub path: Path<'a>,
+    location: PathBuf,
+}
+
+impl<'a> Archived<'a> {
+    fn location(path: &Path) -> PathBuf {
+        archive_path(path.root())
+            .join(&path.host)
+            .join(&path.owner)
+            .join(format!("{}.{}", path.repo, EXTENSION))
+    }
+
+    /// Packs the repository into an archive, then removes its working directory.
+    pub fn create(path: &Path<'a>) -> Result<Self> {
+        let dir = PathBuf::from(path);
+        if !dir.is_dir() {
+            bail!("The repository does not exist: {}", dir.to_string_lossy());
+        }
+
+        if dir.is_symlink() {
+            bail!(
+                "Linked repositories cannot be archived: {}",
+                dir.to_string_lossy(),
+            );
+        }
+
+        let location = Self::location(path);
+        if location.exists() {
+            bail!(
+                "The repository is already archived: {}",
+                location.to_string_lossy(),
+            );
+        }
+
+        create_dir_all(
+            location
+                .parent()
+                .ok_or_else(|| anyhow!("Could not determine the parent directory"))?,
+        )?;
+
+        // Writes into a temporary file first, not to leave a broken archive on failures.
+        let temporary = location.with_extension("partial");
+        let result = (|| {
+            let mut builder = Builder::new(GzEncoder::new(
+                File::create(&temporary)?,
+                Compression::default(),
+            ));
+            builder.follow_symlinks(false);
+            builder.append_dir_all(".", &dir)?;
+            builder.into_inner()?.finish()?.sync_all()?;
+
+            Ok::<_, anyhow::Error>(())
+        })();
+
+        if let Err(e) = result {
+            let _ = remove_file(&temporary);
+            return Err(e);
+        }
+
+        rename(&temporary, &location)?;
+        remove_dir_all(&dir)?;
+        Index::remove(path)?;
+
+        Ok(Self {
+            path: Path::new(path.root(), &path.host, &path.owner, &path.repo),
+            location,
+        })
+    }
+
+    /// Lists the archived repositories in all roots.
+    pub fn list(roots: &'a Roots) -> Result<Vec<Self>> {
+        let mut archived = Vec::new();
+        for root in roots.iter() {
+            let dir = archive_path(root);
+            if !dir.is_dir() {
+                continue;
+            }
+
+            for entry in WalkDir::new(&dir)
+                .min_depth(3)
+                .max_depth(3)
+                .sort_by_file_name()
+            {
+                let entry = entry?;
+                let parts = entry
+                    .path()
+                    .strip_prefix(&dir)?
+                    .iter()
+                    .map(|c| c.to_string_lossy().to_string())
+                    .collect::<Vec<_>>();
+
+                let [host, owner, file] = parts.as_slice() else {
+                    continue;
+                };
+
+                if let Some(repo) = file.strip_suffix(&format!(".{}", EXTENSION)) {
+                    archived.push(Self {
+                        path: Path::new(root, host, owner, repo),
+                        location: entry.into_path(),
+                    });
+                }
+            }
+        }
+
+        Ok(archived)
+    }
+
+    /// Size of the archive in bytes.
+    pub fn size(&self) -> Result<u64> {
+        Ok(self.location.metadata()?.len())
+    }
+
+    /// Unpacks the archive into the original location, then removes the archive.
+    pub fn extract(self) -> Result<Path<'a>> {
+        let to = PathBuf::from(&self.path);
+        if to.exists() {
+            bail!(
+                "The original location is already taken: {}",
+                to.to_string_lossy(),
+            );
+        }
+
+        create_dir_all(&to)?;
+
+        let mut archive = Archive::new(GzDecoder::new(File::open(&self.location)?));
+        archive.set_preserve_permissions(true);
+        archive.set_preserve_mtime(true);
+        if let Err(e) = archive.unpack(&to) {
+            let _ = remove_dir_all(&to);
+            return Err(e.into());
+        }
+
+        remove_file(&self.location)?;
+        Index::insert(&self.path)?;
+
+        // Removes the owner and host directories left empty in the archive.
+        if let Some(parent) = self.location.parent() {
+            prune_empty(parent, &archive_path(self.path.root()))?;
+        }
+
+        Ok(self.path)
+    }
+}
@@ -1,4 +1,3 @@
-use std::fs::{create_dir_all, rename};
 use std::path::PathBuf;
 
 use anyhow::{Result, anyhow, bail};
@@ -9,11 +8,42 @@ use git2::{Remote, Repository};
 use itertools::Itertools;
 use tracing::info;
 
+use crate::cmd::import::{Placement, transfer};
 use crate::config::Config;
+use crate::index::Index;
 use crate::path::Path;
-use crate::root::Root;
+use crate::root::Roots;
 use crate::url::Url;
 
+/// Finds the URL of the repository from the remote of the name,
+/// or the first remote in a supported form if no name is given.
+pub(super) fn find_url(config: &Config, repo: &Repository, remote: Option<&str>) -> Result<Url> {
+    if let Some(name) = remote {
+        let remote = repo.find_remote(name)?;
+        let url = remote
+            .url()
+            .ok_or_else(|| anyhow!("The remote {} has no URL.", name))?;
+
+        return Url::from_str(url, &config.patterns, config.defaults.owner.as_deref());
+    }
+
+    let remotes: Vec<Remote> = repo
+        .remotes()?
+        .iter()
+        .flatten()
+        .map(|r| repo.find_remote(r))
+        .try_collect()?;
+
+    match remotes
+        .iter()
+        .filter_map(|r| r.url())
+        .find_map(|u| Url::from_str(u, &config.patterns, config.defaults.owner.as_deref()).ok())
+    {
+        Some(u) => Ok(u),
+        _ => bail!("Could not find a supported remote in the repository."),
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct Cmd {
     /// Path to the repository to add.
@@ -23,6 +53,19 @@ pub struct Cmd {
     #[clap(short, long)]
     force: bool,
 
+    /// Copies the repository instead of moving it, leaving the original in place.
+    #[clap(long, conflicts_with = "symlink")]
+    copy: bool,
+
+    /// Links to the repository from the root instead of moving it, leaving the original in place.
+    #[clap(long)]
+    symlink: bool,
+
+    /// Name of the remote to determine the location of the repository.
+    /// Defaults to the first remote in a supported form.
+    #[clap(long)]
+    remote: Option<String>,
+
     /// Change directory after added the repository. (Shell extension required)
     #[clap(long)]
     cd: bool,
@@ -34,41 +77,42 @@ pub struct Cmd {
 
 impl Cmd {
     pub fn run(self) -> Result<()> {
-        let root = Root::find()?;
-        let config = Config::load_from(&root)?;
-
-        let repo = Repository::open(&self.repo)?;
-        let remotes: Vec<Remote> = repo
-            .remotes()?
-            .iter()
-            .flatten()
-            .map(|r| repo.find_remote(r))
-            .try_collect()?;
-
-        let url =
-            match remotes.iter().filter_map(|r| r.url()).find_map(|u| {
-                Url::from_str(u, &config.patterns, config.defaults.owner.as_deref()).ok()
-            }) {
-                Some(u) => u,
-                _ => bail!("Could not find a supported remote in the repository."),
-            };
-
-        let _ = repo; // Closing the repository
-
-        let path = Path::resolve(&root, &url);
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let from = PathBuf::from(&self.repo).canonicalize()?;
+        let url = find_url(&config, &Repository::open(&from)?, self.remote.as_deref())?;
+
+        let target = Path::resolve(roots.resolve(&url, &config.rules)?, &url);
         let profile = config
             .rules
             .resolve(&url)
-            .and_then(|r| config.profiles.resolve(&r.profile));
+            .and_then(|r| r.profile.as_ref())
+            .and_then(|p| config.profiles.resolve(p));
 
-        let path = PathBuf::from(&path);
+        let path = PathBuf::from(&target);
+
+        let placement = match (self.copy, self.symlink) {
+            (true, _) => Placement::Copy,
+            (_, true) => Placement::Symlink,
+            _ => Placement::Move,
+        };
 
         info!("URL of the repository is: {}", url.to_string());
         info!(
-            "This will move entire the repository to: {}",
+            "{}: {}",
+            match placement {
+                Placement::Move => "This will move entire the repository to",
+                Placement::Copy => "This will copy entire the repository to",
+                Placement::Symlink => "This will link to the repository from",
+            },
             path.to_string_lossy(),
         );
 
+        if path.exists() {
+            bail!("The destination already exists: {}", path.to_string_lossy());
+        }
+
         if !self.force
             && !Confirm::new()
                 .with_prompt(format!(
@@ -80,19 +124,13 @@ impl Cmd {
             return Ok(());
         }
 
-        let parent_path = path
-            .parent()
-            .ok_or_else(|| {
-                anyhow!(
-                    "Failed to determine parent path for the repository's new location: {}",
-                    path.to_string_lossy()
-                )
-            })?
-            .to_path_buf();
+        transfer(&from, &path, placement)?;
+        if placement == Placement::Move {
+            roots.prune(&from)?;
+        }
 
-        create_dir_all(parent_path)?;
+        Index::insert(&target)?;
 
-        rename(&self.repo, &path)?;
         info!(
             "Added the repository successfully to: {}",
             path.to_string_lossy(),
@@ -100,7 +138,7 @@ impl Cmd {
 
         let repo = Repository::open(&path)?;
         if let Some((name, p)) = profile {
-            p.apply(&repo)?;
+            p.apply(name, &repo)?;
 
             info!("Attached profile [{}] successfully.", style(name).bold());
         }
@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use console::style;
+use dialoguer::Confirm;
+use itertools::Itertools;
+use tracing::{info, warn};
+
+use crate::archive::Archived;
+use crate::config::Config;
+use crate::console::Spinner;
+use crate::path::Path;
+use crate::repository::Repositories;
+use crate::root::Roots;
+use crate::selector::Selector;
+use crate::url::Url;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// URLs or patterns of the repositories to archive,
+    /// or selector terms such as `owner:acme stale:1y`.
+    #[clap(required = true)]
+    repo: Vec<String>,
+
+    /// Forces to archive the repositories without any prompt.
+    #[clap(short, long)]
+    force: bool,
+}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let (selector, rest) = Selector::split(&self.repo.join(" "))?;
+        if selector.is_empty() && rest.is_empty() {
+            bail!("No repositories or selector terms are given.");
+        }
+
+        let targets = match (selector.is_empty(), rest.is_empty()) {
+            (false, true) => Repositories::try_collect(&roots)?
+                .select(&selector, &config.rules)
+                .into_iter()
+                .map(|(p, _)| p)
+                .sorted_by_key(|p| p.to_string())
+                .collect::<Vec<_>>(),
+            (true, _) => self
+                .repo
+                .iter()
+                .map(|repo| {
+                    let url =
+                        Url::from_str(repo, &config.patterns, config.defaults.owner.as_deref())?;
+
+                    Ok(Path::resolve(roots.resolve(&url, &config.rules)?, &url))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => bail!("Repositories and selector terms cannot be mixed: {}", rest),
+        };
+
+        if targets.is_empty() {
+            info!("No repositories matched the selector.");
+            return Ok(());
+        }
+
+        targets
+            .iter()
+            .for_each(|p| println!("{}", PathBuf::from(p).to_string_lossy()));
+
+        if !self.force
+            && !Confirm::new()
+                .with_prompt(format!(
+                    "{} {} repositories will be packed into archives, and their working directories removed. Are you sure want to continue?",
+                    style("CHECK").dim(),
+                    targets.len(),
+                ))
+                .interact()?
+        {
+            return Ok(());
+        }
+
+        let mut archived = 0;
+        for target in &targets {
+            match Spinner::new("Archiving the repository...")
+                .spin_while(|| async { Archived::create(target) })
+                .await
+            {
+                Ok(a) => {
+                    roots.prune(&PathBuf::from(target))?;
+                    info!("Archived the repository successfully: {}", a.path);
+                    archived += 1;
+                }
+                Err(e) => warn!("Could not archive {}: {}", target, e),
+            }
+        }
+
+        if archived < targets.len() {
+            bail!(
+                "Could not archive {} repositories.",
+                targets.len() - archived,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -14,8 +14,9 @@ use tracing::{info, warn};
 use crate::config::Config;
 use crate::console::{MultiSpinner, Spinner};
 use crate::git::{CloneOptions, CloneRepository};
+use crate::index::Index;
 use crate::path::Path;
-use crate::root::Root;
+use crate::root::Roots;
 use crate::url::Url;
 
 // Constant values taken from implementation of GitHub Cli (gh)
@@ -63,8 +64,8 @@ pub struct Cmd {
 
 impl Cmd {
     pub async fn run(self) -> Result<()> {
-        let root = Root::find()?;
-        let config = Config::load_from(&root)?;
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
 
         let urls = self
             .repo
@@ -74,8 +75,8 @@ impl Cmd {
             .await?;
 
         match self.parallel {
-            true => self.clone_parallel(root, config, urls).await?,
-            _ => self.clone_serial(&root, &config, urls).await?,
+            true => self.clone_parallel(roots, config, urls).await?,
+            _ => self.clone_serial(&roots, &config, urls).await?,
         }
         .into_iter()
         .for_each(
@@ -132,7 +133,7 @@ impl Cmd {
 
     async fn clone_serial(
         self,
-        root: &Root,
+        roots: &Roots,
         config: &Config,
         urls: Vec<Url>,
     ) -> Result<Vec<CloneResult>> {
@@ -141,7 +142,7 @@ impl Cmd {
                 urls.into_iter()
                     .async_map(|url| async {
                         info!("Cloning from '{}'", url.to_string());
-                        self.clone(root, config, url).await
+                        self.clone(roots, config, url).await
                     })
                     .collect::<Result<Vec<_>>>()
                     .await
@@ -151,35 +152,37 @@ impl Cmd {
 
     async fn clone_parallel(
         self,
-        root: Root,
+        roots: Roots,
         config: Config,
         urls: Vec<Url>,
     ) -> Result<Vec<CloneResult>> {
         let this = Arc::new(self);
-        let root = Arc::new(root);
+        let roots = Arc::new(roots);
         let config = Arc::new(config);
 
         let mut spinner = MultiSpinner::new();
         for url in urls {
             let this = Arc::clone(&this);
-            let root = Arc::clone(&root);
+            let roots = Arc::clone(&roots);
             let config = Arc::clone(&config);
 
             spinner = spinner
                 .with_spin_while(format!("Cloning from {}...", &url), move || async move {
-                    this.as_ref().clone(&root, &config, url).await
+                    this.as_ref().clone(&roots, &config, url).await
                 });
         }
 
         Ok(spinner.collect().await?.into_iter().collect())
     }
 
-    async fn clone(&self, root: &Root, config: &Config, url: Url) -> Result<CloneResult> {
-        let path = PathBuf::from(Path::resolve(root, &url));
+    async fn clone(&self, roots: &Roots, config: &Config, url: Url) -> Result<CloneResult> {
+        let target = Path::resolve(roots.resolve(&url, &config.rules)?, &url);
+        let path = PathBuf::from(&target);
         let profile = config
             .rules
             .resolve(&url)
-            .and_then(|r| config.profiles.resolve(&r.profile));
+            .and_then(|r| r.profile.as_ref())
+            .and_then(|p| config.profiles.resolve(p));
 
         if path.exists() {
             warn!("Directory already exists. Skipping cloning the repository...");
@@ -208,9 +211,11 @@ impl Cmd {
             }
         }
 
+        Index::insert(&target)?;
+
         let repo = Repository::open(&path)?;
         let profile = if let Some((name, p)) = profile {
-            p.apply(&repo)?;
+            p.apply(name, &repo)?;
             Some(name.to_string())
         } else {
             None
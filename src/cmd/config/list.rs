@@ -0,0 +1,25 @@
+use anyhow::Result;
+use clap::Parser;
+use console::style;
+
+use crate::root::Root;
+use crate::subscription::Subscriptions;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let root = Root::find()?;
+
+        Subscriptions::list(&root)?.into_iter().for_each(|s| {
+            println!(
+                "{} {}",
+                s.name,
+                style(format!("({})", s.path.to_string_lossy())).dim(),
+            );
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+mod list;
+mod subscribe;
+mod unsubscribe;
+mod update;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Subcommand)]
+pub enum Action {
+    /// Lists all subscribed configuration repositories.
+    List(list::Cmd),
+    /// Subscribes a configuration repository shared in your team.
+    Subscribe(subscribe::Cmd),
+    /// Unsubscribes a configuration repository.
+    Unsubscribe(unsubscribe::Cmd),
+    /// Pulls the latest versions of the subscribed configuration repositories.
+    Update(update::Cmd),
+}
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    #[clap(subcommand)]
+    action: Action,
+}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        use Action::*;
+        match self.action {
+            List(cmd) => cmd.run(),
+            Subscribe(cmd) => cmd.run().await,
+            Unsubscribe(cmd) => cmd.run(),
+            Update(cmd) => cmd.run().await,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::fs::remove_dir_all;
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::console::Spinner;
+use crate::git::{CloneOptions, CloneRepository};
+use crate::root::{Root, prune_empty};
+use crate::subscription::Subscription;
+use crate::url::Url;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// URL or pattern of the configuration repository to subscribe.
+    repo: String,
+}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        let root = Root::find()?;
+        let config = Config::load_from(&root)?;
+
+        let url = Url::from_str(
+            &self.repo,
+            &config.patterns,
+            config.defaults.owner.as_deref(),
+        )?;
+
+        let subscription = Subscription::resolve(&root, &url);
+        if subscription.path.exists() {
+            bail!("Already subscribed the repository: {}", subscription.name);
+        }
+
+        Spinner::new("Cloning the configuration repository...")
+            .spin_while(|| async {
+                config.git.strategy.clone.clone_repository(
+                    url.clone(),
+                    &subscription.path,
+                    &CloneOptions::default(),
+                )
+            })
+            .await?;
+
+        if !subscription.config_path().exists() {
+            warn!(
+                "The repository does not contain ghr.toml. Nothing will be merged until it is added.",
+            );
+        }
+
+        // Verifies the merged configuration is still valid, removing the clone otherwise.
+        if let Err(e) = Config::check_subscription(&root, &subscription) {
+            remove_dir_all(&subscription.path)?;
+            prune_empty(subscription.path.parent().unwrap(), &root.data_path())?;
+
+            return Err(e.context(format!(
+                "Could not subscribe the broken configuration: {}",
+                subscription.name,
+            )));
+        }
+
+        info!(
+            "Subscribed the repository successfully: {}",
+            subscription.name
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use std::fs::remove_dir_all;
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use tracing::info;
+
+use crate::config::Config;
+use crate::root::Root;
+use crate::subscription::Subscription;
+use crate::url::Url;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// URL or pattern of the configuration repository to unsubscribe.
+    repo: String,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let root = Root::find()?;
+        // Subscriptions are not merged, as the one to unsubscribe might be broken.
+        let config = Config::load_from_without_subscriptions(&root)?;
+
+        let url = Url::from_str(
+            &self.repo,
+            &config.patterns,
+            config.defaults.owner.as_deref(),
+        )?;
+
+        let subscription = Subscription::resolve(&root, &url);
+        if !subscription.path.exists() {
+            bail!("Not subscribed the repository: {}", subscription.name);
+        }
+
+        remove_dir_all(&subscription.path)?;
+
+        info!(
+            "Unsubscribed the repository successfully: {}",
+            subscription.name,
+        );
+
+        Ok(())
+    }
+}
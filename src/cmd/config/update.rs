@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use anyhow::Result;
+use clap::Parser;
+use git2::{Oid, Repository, ResetType};
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::console::Spinner;
+use crate::git::Pull;
+use crate::root::Root;
+use crate::subscription::Subscriptions;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        let root = Root::find()?;
+        let config = Config::load_from(&root)?;
+
+        for subscription in Subscriptions::list(&root)? {
+            let head = match Repository::open(&subscription.path)
+                .and_then(|r| Ok(r.head()?.peel_to_commit()?.id()))
+            {
+                Ok(h) => h,
+                Err(e) => {
+                    warn!(
+                        "Could not read the subscription {}: {}",
+                        subscription.name, e,
+                    );
+                    continue;
+                }
+            };
+
+            let result = Spinner::new(format!("Pulling {}...", subscription.name))
+                .spin_while(|| async { config.git.strategy.pull.pull(&subscription.path) })
+                .await
+                // Verifies the merged configuration is still valid.
+                .and_then(|_| Config::check_subscription(&root, &subscription));
+
+            match result {
+                Ok(_) => info!("Updated the subscription: {}", subscription.name),
+                Err(e) => {
+                    warn!(
+                        "Could not update the subscription {}: {:#}",
+                        subscription.name, e,
+                    );
+
+                    // Rolls back to the previous version, not to break the configuration.
+                    if let Err(e) = reset(&subscription.path, head) {
+                        warn!(
+                            "Could not roll back the subscription {}: {}",
+                            subscription.name, e,
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn reset(path: &Path, head: Oid) -> Result<(), git2::Error> {
+    let repo = Repository::open(path)?;
+    repo.reset(repo.find_commit(head)?.as_object(), ResetType::Hard, None)
+}
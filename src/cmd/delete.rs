@@ -1,47 +1,156 @@
 use std::future::ready;
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
+use chrono::Utc;
 use clap::Parser;
 use console::style;
 use dialoguer::Confirm;
-use tracing::info;
+use itertools::Itertools;
+use tracing::{info, warn};
 
 use crate::config::Config;
 use crate::console::Spinner;
+use crate::git::inspect::inspect;
+use crate::index::Index;
 use crate::path::Path;
-use crate::root::Root;
+use crate::repository::Repositories;
+use crate::root::Roots;
+use crate::selector::Selector;
+use crate::trash::Trashed;
 use crate::url::Url;
 
 #[derive(Debug, Parser)]
 pub struct Cmd {
     /// URL or pattern of the repository to delete.
+    #[clap(required_unless_present = "select")]
     repo: Vec<String>,
+
+    /// Deletes all repositories matching the selector, such as `owner:acme stale:1y`.
+    #[clap(long, conflicts_with = "repo")]
+    select: Option<String>,
+
+    /// Deletes the repositories even with unpushed work, or protected ones given by name.
+    #[clap(short, long)]
+    force: bool,
+
+    /// Deletes the repositories permanently instead of moving them into the trash.
+    #[clap(long)]
+    permanent: bool,
 }
 
 impl Cmd {
     pub async fn run(self) -> Result<()> {
-        let root = Root::find()?;
-        let config = Config::load_from(&root)?;
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let targets = match &self.select {
+            Some(select) => {
+                let selector = Selector::parse(&[select])?;
+                if selector.is_empty() {
+                    bail!("The selector is empty. Give at least one term, such as `owner:acme`.");
+                }
+
+                let targets = Repositories::try_collect(&roots)?
+                    .select(&selector, &config.rules)
+                    .into_iter()
+                    .map(|(p, _)| p)
+                    .filter(|p| match config.rules.is_protected(&p.to_url()) {
+                        true => {
+                            warn!("Skipped the protected repository: {}", p);
+                            false
+                        }
+                        _ => true,
+                    })
+                    .sorted_by_key(|p| p.to_string())
+                    .collect::<Vec<_>>();
+
+                if targets.is_empty() {
+                    info!("No repositories matched the selector.");
+                    return Ok(());
+                }
+
+                targets
+                    .iter()
+                    .for_each(|p| println!("{}", PathBuf::from(p).to_string_lossy()));
+
+                targets
+            }
+            _ => self
+                .repo
+                .iter()
+                .map(|repo| {
+                    let url =
+                        Url::from_str(repo, &config.patterns, config.defaults.owner.as_deref())?;
+
+                    Ok(Path::resolve(roots.resolve(&url, &config.rules)?, &url))
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+
+        let mut refused = 0;
+        for target in &targets {
+            let mut reasons = Vec::new();
+            if config.rules.is_protected(&target.to_url()) {
+                reasons.push("protected by the rules".to_string());
+            }
+
+            match inspect(PathBuf::from(target)) {
+                Ok(findings) => reasons.extend(findings.iter().map(|f| f.to_string())),
+                Err(e) => reasons.push(format!("could not be inspected: {}", e)),
+            }
+
+            if !reasons.is_empty() {
+                warn!("{} cannot be deleted safely:", target);
+                reasons.iter().for_each(|r| warn!("  - {}", r));
+                refused += 1;
+            }
+        }
+
+        if refused > 0 && !self.force {
+            bail!(
+                "Refused to delete {} repositories. Use --force to delete them anyway.",
+                refused,
+            );
+        }
 
         if !Confirm::new()
             .with_prompt(format!(
-                "{} Content of the repository will be deleted permanently. Are you sure want to continue?",
+                "{} {} Are you sure want to continue?",
                 style("CHECK").dim(),
+                match self.permanent {
+                    true => "Content of the repository will be deleted permanently.",
+                    _ => "The repository will be moved into the trash.",
+                },
             ))
             .interact()?
         {
             return Ok(());
         }
 
-        for repo in self.repo.iter() {
-            let url = Url::from_str(repo, &config.patterns, config.defaults.owner.as_deref())?;
-            let path = PathBuf::from(Path::resolve(&root, &url));
+        let deleted_at = Utc::now();
+        for target in targets {
+            let path = PathBuf::from(&target);
+
+            if !self.permanent {
+                Trashed::put(&target, deleted_at)?;
+                roots.prune(&path)?;
+
+                info!(
+                    "Moved the repository into the trash: {} (restore it by `ghr trash restore {}`)",
+                    path.to_string_lossy(),
+                    target,
+                );
+                continue;
+            }
 
             Spinner::new("Deleting the repository...")
                 .spin_while(|| ready(std::fs::remove_dir_all(&path).map_err(anyhow::Error::from)))
                 .await?;
 
+            Index::remove(&target)?;
+            roots.prune(&path)?;
+
             info!(
                 "Deleted the repository successfully: {}",
                 path.to_string_lossy(),
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use itertools::Itertools;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::repository::Repositories;
+use crate::root::Roots;
+use crate::selector::Selector;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Terms to filter repositories to run the command in, such as `owner:acme dirty:true`.
+    selector: Vec<String>,
+
+    /// Command and its arguments to run, following `--`.
+    #[clap(last = true, required = true)]
+    command: Vec<String>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let selector = Selector::parse(&self.selector)?;
+
+        let mut failures = 0;
+        for path in Repositories::try_collect(&roots)?
+            .select(&selector, &config.rules)
+            .into_iter()
+            .map(|(p, _)| p)
+            .sorted_by_key(|p| p.to_string())
+        {
+            info!("Running in {}", path);
+
+            let status = Command::new(&self.command[0])
+                .args(&self.command[1..])
+                .current_dir(PathBuf::from(&path))
+                .status();
+
+            match status {
+                Ok(s) if s.success() => (),
+                Ok(s) => {
+                    warn!("The command exited with {} in {}", s, path);
+                    failures += 1;
+                }
+                Err(e) => {
+                    warn!("Could not run the command in {}: {}", path, e);
+                    failures += 1;
+                }
+            }
+        }
+
+        if failures > 0 {
+            bail!("The command failed in {} repositories", failures);
+        }
+
+        Ok(())
+    }
+}
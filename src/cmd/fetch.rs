@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use git2::Repository as GitRepository;
+use itertools::Itertools;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::console::Spinner;
+use crate::git::Fetch;
+use crate::repository::Repositories;
+use crate::root::Roots;
+use crate::selector::Selector;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Terms to filter repositories to fetch, such as `owner:acme name:api-*`.
+    selector: Vec<String>,
+}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let selector = Selector::parse(&self.selector)?;
+
+        let mut failures = 0;
+        for path in Repositories::try_collect(&roots)?
+            .select(&selector, &config.rules)
+            .into_iter()
+            .map(|(p, _)| p)
+            .sorted_by_key(|p| p.to_string())
+        {
+            let dir = PathBuf::from(&path);
+            let result = Spinner::new(format!("Fetching {}...", path))
+                .spin_while(|| async {
+                    let repo = GitRepository::open(&dir)?;
+                    for remote in repo.remotes()?.iter().flatten() {
+                        config.git.strategy.fetch.fetch(&dir, remote)?;
+                    }
+
+                    Ok::<(), anyhow::Error>(())
+                })
+                .await;
+
+            match result {
+                Ok(_) => info!("Fetched the repository: {}", path),
+                Err(e) => {
+                    warn!("Could not fetch the repository {}: {}", path, e);
+                    failures += 1;
+                }
+            }
+        }
+
+        if failures > 0 {
+            bail!("Failed to fetch {} repositories", failures);
+        }
+
+        Ok(())
+    }
+}
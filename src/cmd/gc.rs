@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::fs::{read_dir, remove_dir};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::Utc;
+use clap::Parser;
+use console::style;
+use dialoguer::Confirm;
+use itertools::Itertools;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+use crate::config::Config;
+use crate::path::Path;
+use crate::root::{Root, Roots};
+use crate::trash::Trashed;
+
+/// Directories to clean up in a root.
+#[derive(Default)]
+struct Garbage {
+    /// Non-empty directories placed as repositories, but not Git repositories.
+    leaves: Vec<PathBuf>,
+    /// Directories that are or will become empty, the deepest first.
+    empties: Vec<PathBuf>,
+}
+
+impl Garbage {
+    fn find(root: &Root, excludes: &[&std::path::Path]) -> Result<Self> {
+        let depth = root.layout().depth();
+        let mut garbage = Self::default();
+        let mut removed = HashSet::new();
+
+        // Visits the entries in the reverse order of walking, so children come before their parents
+        // and it is known whether the parents will be left empty.
+        let entries = WalkDir::new(root.path())
+            .min_depth(1)
+            .max_depth(depth)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                let hidden =
+                    entry.depth() == 1 && entry.file_name().to_string_lossy().starts_with('.');
+
+                !hidden && !excludes.contains(&entry.path())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for entry in entries.into_iter().rev() {
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+
+            let path = entry.into_path();
+            let empty = read_dir(&path)?
+                .map(|e| e.map(|e| removed.contains(&e.path())))
+                .fold_ok(true, |all, r| all && r)?;
+
+            if empty {
+                garbage.empties.push(path.clone());
+                removed.insert(path);
+            } else if is_leaf(&path, root, depth) && !path.join(".git").exists() {
+                garbage.leaves.push(path.clone());
+                removed.insert(path);
+            }
+        }
+
+        Ok(garbage)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.leaves.is_empty() && self.empties.is_empty()
+    }
+}
+
+fn is_leaf(path: &std::path::Path, root: &Root, depth: usize) -> bool {
+    path.strip_prefix(root.path())
+        .map(|p| p.components().count() == depth)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Prints the directories to clean up without touching them.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Forces to clean up without any prompt.
+    #[clap(short, long)]
+    force: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let garbages = roots
+            .iter()
+            .map(|root| Ok((root, Garbage::find(root, &roots.others(root))?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        if garbages.iter().all(|(_, g)| g.is_empty()) {
+            info!("Nothing to clean up.");
+            return Ok(());
+        }
+
+        for (_, garbage) in &garbages {
+            for leaf in &garbage.leaves {
+                info!("Not a repository: {}", style(leaf.to_string_lossy()).bold());
+            }
+            for empty in &garbage.empties {
+                info!("Empty directory: {}", empty.to_string_lossy());
+            }
+        }
+
+        let (leaves, empties) = garbages.iter().fold((0, 0), |(l, e), (_, g)| {
+            (l + g.leaves.len(), e + g.empties.len())
+        });
+
+        if self.dry_run
+            || !self.force
+                && !Confirm::new()
+                    .with_prompt(format!(
+                        "{} {} directories will be moved into the trash, and {} empty ones removed. Are you sure want to continue?",
+                        style("CHECK").dim(),
+                        leaves,
+                        empties,
+                    ))
+                    .interact()?
+        {
+            return Ok(());
+        }
+
+        let deleted_at = Utc::now();
+        for (root, garbage) in &garbages {
+            for leaf in &garbage.leaves {
+                let Some((host, owner, repo)) = leaf
+                    .strip_prefix(root.path())
+                    .ok()
+                    .and_then(|p| root.layout().parse(p))
+                else {
+                    warn!(
+                        "Skipped a directory not matching the layout: {}",
+                        leaf.to_string_lossy(),
+                    );
+                    continue;
+                };
+
+                if let Err(e) = Trashed::put(&Path::new(root, host, owner, repo), deleted_at) {
+                    warn!(
+                        "Could not move {} into the trash: {}",
+                        leaf.to_string_lossy(),
+                        e
+                    );
+                }
+            }
+
+            for empty in &garbage.empties {
+                if let Err(e) = remove_dir(empty) {
+                    warn!("Could not remove {}: {}", empty.to_string_lossy(), e);
+                }
+            }
+        }
+
+        info!("Cleaned up the roots successfully.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,398 @@
+use std::collections::HashSet;
+use std::fs::{
+    File, copy, create_dir, create_dir_all, read_link, remove_dir_all, rename, set_permissions,
+};
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow, bail};
+use clap::Parser;
+use console::style;
+use dialoguer::Confirm;
+use git2::Repository;
+use tracing::{debug, info, warn};
+use walkdir::WalkDir;
+
+use crate::cmd::add::find_url;
+use crate::config::Config;
+use crate::index::Index;
+use crate::path::Path;
+use crate::root::{Roots, prune_empty};
+use crate::url::Url;
+
+/// Finds Git repositories in the directory, without descending into them.
+pub(super) fn scan(dir: &std::path::Path, excludes: &[&std::path::Path]) -> Result<Vec<PathBuf>> {
+    let mut repos = Vec::new();
+    let mut walker = WalkDir::new(dir).sort_by_file_name().into_iter();
+    while let Some(entry) = walker.next() {
+        let entry = entry?;
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+
+        if excludes.contains(&entry.path()) {
+            walker.skip_current_dir();
+            continue;
+        }
+
+        if entry.path().join(".git").exists() {
+            repos.push(entry.into_path());
+            walker.skip_current_dir();
+        }
+    }
+
+    Ok(repos)
+}
+
+/// How to place a repository into the root.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum Placement {
+    /// Moves the repository, falling back to copying when it is on another filesystem.
+    Move,
+    /// Copies the repository, leaving the original in place.
+    Copy,
+    /// Leaves the repository where it is, and links to it from the root.
+    Symlink,
+}
+
+#[cfg(unix)]
+fn symlink(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(from, to)
+}
+
+#[cfg(windows)]
+fn symlink(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+    match to
+        .parent()
+        .map(|p| p.join(from))
+        .is_some_and(|p| p.is_dir())
+    {
+        true => std::os::windows::fs::symlink_dir(from, to),
+        _ => std::os::windows::fs::symlink_file(from, to),
+    }
+}
+
+/// Copies the directory recursively, keeping symbolic links and permissions as they are.
+/// Permissions of directories are set after copying, deepest first, to write into read-only ones.
+fn copy_dir(from: &std::path::Path, to: &std::path::Path) -> Result<()> {
+    let mut dirs = Vec::new();
+    for entry in WalkDir::new(from) {
+        let entry = entry?;
+        let dst = to.join(entry.path().strip_prefix(from)?);
+        let file_type = entry.file_type();
+
+        if file_type.is_symlink() {
+            symlink(&read_link(entry.path())?, &dst)?;
+        } else if file_type.is_dir() {
+            create_dir(&dst)?;
+            dirs.push((dst, entry.metadata()?.permissions()));
+        } else if file_type.is_file() {
+            copy(entry.path(), &dst)?;
+        } else {
+            bail!(
+                "Could not copy a special file such as a FIFO or socket: {}",
+                entry.path().to_string_lossy(),
+            );
+        }
+    }
+
+    for (dir, permissions) in dirs.into_iter().rev() {
+        set_permissions(&dir, permissions)?;
+    }
+
+    Ok(())
+}
+
+/// Lists entries in the directory with their sizes and link targets, to compare copies.
+fn snapshot(dir: &std::path::Path) -> Result<Vec<(PathBuf, u64, Option<PathBuf>)>> {
+    WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .map(|entry| {
+            let entry = entry?;
+            let link = match entry.file_type().is_symlink() {
+                true => Some(read_link(entry.path())?),
+                _ => None,
+            };
+            let size = match entry.file_type().is_file() {
+                true => entry.metadata()?.len(),
+                _ => 0,
+            };
+
+            Ok((entry.path().strip_prefix(dir)?.to_path_buf(), size, link))
+        })
+        .collect()
+}
+
+/// Compares contents of the files chunk by chunk, without reading them into memory at once.
+fn same_contents(a: &std::path::Path, b: &std::path::Path) -> Result<bool> {
+    let mut a = BufReader::new(File::open(a)?);
+    let mut b = BufReader::new(File::open(b)?);
+    loop {
+        let (x, y) = (a.fill_buf()?, b.fill_buf()?);
+        if x.is_empty() || y.is_empty() {
+            return Ok(x.is_empty() && y.is_empty());
+        }
+
+        let n = x.len().min(y.len());
+        if x[..n] != y[..n] {
+            return Ok(false);
+        }
+
+        a.consume(n);
+        b.consume(n);
+    }
+}
+
+/// Verifies the copy has the same entries as the original, and the files have the same contents.
+fn verify(from: &std::path::Path, to: &std::path::Path) -> Result<bool> {
+    if snapshot(from)? != snapshot(to)? {
+        return Ok(false);
+    }
+
+    for entry in WalkDir::new(from) {
+        let entry = entry?;
+        if entry.file_type().is_file()
+            && !same_contents(entry.path(), &to.join(entry.path().strip_prefix(from)?))?
+        {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Copies the directory, then verifies the copy matches the original.
+/// The copy is removed if it could not be completed.
+fn copy_verified(from: &std::path::Path, to: &std::path::Path) -> Result<()> {
+    let result = copy_dir(from, to).and_then(|_| match verify(from, to)? {
+        true => Ok(()),
+        _ => bail!(
+            "The copy does not match the original: {}",
+            to.to_string_lossy()
+        ),
+    });
+
+    if result.is_err() && to.exists() {
+        let _ = remove_dir_all(to);
+    }
+
+    result
+}
+
+/// Transfers the repository to the path as the placement, creating its parent directory.
+pub(super) fn transfer(
+    from: &std::path::Path,
+    to: &std::path::Path,
+    placement: Placement,
+) -> Result<()> {
+    create_dir_all(
+        to.parent()
+            .ok_or_else(|| anyhow!("Could not determine the parent directory"))?,
+    )?;
+
+    match placement {
+        Placement::Move => match rename(from, to) {
+            Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+                debug!(
+                    "Copying the repository as it is on another filesystem: {}",
+                    from.to_string_lossy(),
+                );
+                copy_verified(from, to)?;
+                remove_dir_all(from)?;
+            }
+            r => r?,
+        },
+        Placement::Copy => copy_verified(from, to)?,
+        Placement::Symlink => symlink(&from.canonicalize()?, to)?,
+    }
+
+    Ok(())
+}
+
+/// Places the repository at the target, then attaches the profile chosen by the rules.
+pub(super) fn place(
+    config: &Config,
+    from: &std::path::Path,
+    target: &Path,
+    placement: Placement,
+) -> Result<()> {
+    let to = PathBuf::from(target);
+    transfer(from, &to, placement)?;
+
+    Index::insert(target)?;
+
+    if let Some((name, p)) = config
+        .rules
+        .resolve(&target.to_url())
+        .and_then(|r| r.profile.as_ref())
+        .and_then(|p| config.profiles.resolve(p))
+    {
+        if let Err(e) = Repository::open(&to)
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| p.apply(name, &repo))
+        {
+            warn!("Could not attach profile [{}] to {}: {}", name, target, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Repositories to place into the root, and the number of ones that could not be placed.
+pub(super) struct Plan<'a> {
+    sources: Vec<PathBuf>,
+    moves: Vec<(PathBuf, Path<'a>)>,
+    skipped: usize,
+}
+
+impl<'a> Plan<'a> {
+    /// Resolves destinations of the repositories found in the sources,
+    /// reporting ones that cannot be placed.
+    pub(super) fn new<F>(
+        config: &Config,
+        roots: &'a Roots,
+        sources: Vec<PathBuf>,
+        repos: Vec<PathBuf>,
+        resolve: F,
+    ) -> Self
+    where
+        F: Fn(&std::path::Path) -> Result<Url>,
+    {
+        let mut moves = Vec::new();
+        let mut destinations = HashSet::new();
+        let mut skipped = 0;
+        for from in repos {
+            let target = match resolve(&from)
+                .and_then(|url| Ok(Path::resolve(roots.resolve(&url, &config.rules)?, &url)))
+            {
+                Ok(t) => t,
+                Err(e) => {
+                    warn!("Skipped {}: {}", from.to_string_lossy(), e);
+                    skipped += 1;
+                    continue;
+                }
+            };
+
+            let to = PathBuf::from(&target);
+            if to.exists() || !destinations.insert(to.clone()) {
+                warn!(
+                    "Skipped {}: the destination is already taken: {}",
+                    from.to_string_lossy(),
+                    to.to_string_lossy(),
+                );
+                skipped += 1;
+                continue;
+            }
+
+            info!(
+                "{} -> {}",
+                from.to_string_lossy(),
+                style(to.to_string_lossy()).bold(),
+            );
+
+            moves.push((from, target));
+        }
+
+        Self {
+            sources,
+            moves,
+            skipped,
+        }
+    }
+
+    /// Asks for confirmation, then places the repositories, continuing on failures.
+    pub(super) fn run(
+        self,
+        config: &Config,
+        placement: Placement,
+        dry_run: bool,
+        force: bool,
+    ) -> Result<()> {
+        if self.moves.is_empty() {
+            info!("No repositories to place.");
+            return Ok(());
+        }
+
+        let verb = match placement {
+            Placement::Move => "moved",
+            Placement::Copy => "copied",
+            Placement::Symlink => "linked",
+        };
+
+        if dry_run
+            || !force
+                && !Confirm::new()
+                    .with_prompt(format!(
+                        "{} {} repositories will be {}, and {} skipped. Are you sure want to continue?",
+                        style("CHECK").dim(),
+                        self.moves.len(),
+                        verb,
+                        self.skipped,
+                    ))
+                    .interact()?
+        {
+            return Ok(());
+        }
+
+        let mut placed = 0;
+        for (from, target) in &self.moves {
+            match place(config, from, target, placement) {
+                Ok(_) => placed += 1,
+                Err(e) => {
+                    warn!("Could not place {}: {}", from.to_string_lossy(), e);
+                    continue;
+                }
+            }
+
+            // Cleans up the directories left empty in the source, such as `owner/` of ghq.
+            if let (Placement::Move, Some(parent), Some(source)) = (
+                placement,
+                from.parent(),
+                self.sources.iter().find(|s| from.starts_with(s)),
+            ) {
+                if let Err(e) = prune_empty(parent, source) {
+                    warn!("Could not clean up {}: {}", parent.to_string_lossy(), e);
+                }
+            }
+        }
+
+        info!(
+            "Placed {} repositories successfully, and skipped {}.",
+            placed,
+            self.skipped + self.moves.len() - placed,
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Directory to scan for Git repositories recursively.
+    dir: String,
+
+    /// Prints the moves without performing them.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Forces to move the repositories without any prompt.
+    #[clap(short, long)]
+    force: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let dir = PathBuf::from(&self.dir).canonicalize()?;
+        let excludes = roots.iter().map(|r| r.path().as_path()).collect::<Vec<_>>();
+
+        let repos = scan(&dir, &excludes)?;
+        Plan::new(&config, &roots, vec![dir], repos, |from| {
+            find_url(&config, &Repository::open(from)?, None)
+        })
+        .run(&config, Placement::Move, self.dry_run, self.force)
+    }
+}
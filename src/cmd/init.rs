@@ -1,17 +1,40 @@
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use clap::Parser;
 use console::style;
 use dialoguer::Confirm;
-use git2::Repository;
+use git2::{IndexAddOption, Repository, RepositoryInitOptions};
 use tracing::info;
 
 use crate::config::Config;
+use crate::index::Index;
 use crate::path::Path;
-use crate::root::Root;
+use crate::root::Roots;
 use crate::url::Url;
 
+const INITIAL_COMMIT_MESSAGE: &str = "Initial commit";
+
+/// Commits all files in the working directory as the first commit, with the identity in Git config.
+fn commit_all(repo: &Repository) -> Result<()> {
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.write()?;
+
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = repo.signature()?;
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        INITIAL_COMMIT_MESSAGE,
+        &tree,
+        &[],
+    )?;
+
+    Ok(())
+}
+
 #[derive(Debug, Parser)]
 pub struct Cmd {
     /// URL or pattern of the repository to clone.
@@ -24,25 +47,43 @@ pub struct Cmd {
     /// Opens the directory after cloned a repository.
     #[clap(long)]
     open: Option<Option<String>>,
+
+    /// Name of the template in the configuration to render files from, then commit them.
+    #[clap(long)]
+    template: Option<String>,
 }
 
 impl Cmd {
     pub fn run(self) -> Result<()> {
-        let root = Root::find()?;
-        let config = Config::load_from(&root)?;
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
 
         let url = Url::from_str(
             &self.repo,
             &config.patterns,
             config.defaults.owner.as_deref(),
         )?;
-        let path = Path::resolve(&root, &url);
+        let target = Path::resolve(roots.resolve(&url, &config.rules)?, &url);
         let profile = config
             .rules
             .resolve(&url)
-            .and_then(|r| config.profiles.resolve(&r.profile));
+            .and_then(|r| r.profile.as_ref())
+            .and_then(|p| config.profiles.resolve(p));
+
+        let template = self
+            .template
+            .as_deref()
+            .map(|t| config.templates.resolve(t))
+            .transpose()?;
+
+        let path = PathBuf::from(&target);
+        if template.is_some() && path.exists() {
+            bail!(
+                "Templates can only be rendered into a new repository: {}",
+                path.to_string_lossy(),
+            );
+        }
 
-        let path = PathBuf::from(&path);
         if path.exists()
             && !Confirm::new()
                 .with_prompt(format!(
@@ -54,7 +95,13 @@ impl Cmd {
             return Ok(());
         }
 
-        let repo = Repository::init(&path)?;
+        let repo = match config.defaults.branch.as_deref() {
+            Some(branch) => {
+                Repository::init_opts(&path, RepositoryInitOptions::new().initial_head(branch))?
+            }
+            _ => Repository::init(&path)?,
+        };
+        Index::insert(&target)?;
 
         info!(
             "Initialised a repository successfully in: {}",
@@ -62,11 +109,31 @@ impl Cmd {
         );
 
         if let Some((name, p)) = profile {
-            p.apply(&repo)?;
+            p.apply(name, &repo)?;
 
             info!("Attached profile [{}] successfully.", style(name).bold());
         }
 
+        if let Some(t) = template {
+            let mut fields = vec![
+                ("host".to_string(), url.host.to_string()),
+                ("owner".to_string(), url.owner.clone()),
+                ("repo".to_string(), url.repo.clone()),
+            ];
+            if let Some((_, p)) = profile {
+                fields.extend(p.configs.iter().map(|(k, v)| (k.clone(), v.to_string())));
+            }
+
+            let count = t.render_into(&config.git.strategy.clone, &path, &fields)?;
+            commit_all(&repo)?;
+
+            info!(
+                "Rendered {} files from template [{}], and committed them successfully.",
+                count,
+                style(self.template.as_deref().unwrap_or_default()).bold(),
+            );
+        }
+
         if let Some(app) = self.open {
             config
                 .applications
@@ -1,13 +1,72 @@
 use anyhow::Result;
 use clap::Parser;
 use itertools::Itertools;
+use std::cmp::Reverse;
 use std::path::PathBuf;
 
-use crate::repository::Repositories;
-use crate::root::Root;
+use crate::archive::Archived;
+use crate::config::Config;
+use crate::console::{NONE, print_rows};
+use crate::format::{Format, PathRecord};
+use crate::history::Histories;
+use crate::path::Path;
+use crate::repository::{Metadata, Repositories};
+use crate::root::Roots;
+use crate::selector::Selector;
+
+const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+
+fn human_size(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{} {}", bytes, UNITS[0]),
+        _ => format!("{:.1} {}", size, UNITS[unit]),
+    }
+}
+
+fn long_columns(metadata: Option<&Metadata>, profile: Option<&str>) -> Vec<String> {
+    let profile = profile.unwrap_or(NONE).to_string();
+    let Some(m) = metadata else {
+        return vec![NONE.to_string(); 6]
+            .into_iter()
+            .chain([profile])
+            .collect();
+    };
+
+    vec![
+        m.branch.as_deref().unwrap_or(NONE).to_string(),
+        m.head
+            .as_ref()
+            .map(|c| c.id.to_string())
+            .unwrap_or_else(|| NONE.to_string()),
+        m.head
+            .as_ref()
+            .map(|c| c.time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| NONE.to_string()),
+        match m.dirty {
+            true => "dirty".to_string(),
+            _ => "clean".to_string(),
+        },
+        match m.remotes.is_empty() {
+            true => NONE.to_string(),
+            _ => m.remotes.join(","),
+        },
+        human_size(m.size()),
+        profile,
+    ]
+}
 
 #[derive(Debug, Parser)]
 pub struct Cmd {
+    /// Terms to filter repositories, such as `owner:acme name:api-* dirty:true stale:90d`.
+    selector: Vec<String>,
+
     /// Lists repositories without their hosts.
     #[clap(long)]
     no_host: bool,
@@ -19,20 +78,131 @@ pub struct Cmd {
     /// Lists repositories as full paths instead of their names.
     #[clap(short, long)]
     path: bool,
+
+    /// Lists repositories with their branch, HEAD commit, state, remotes, size and profile.
+    #[clap(short, long)]
+    long: bool,
+
+    /// Lists only visited repositories, most recently visited first.
+    #[clap(short, long)]
+    recent: bool,
+
+    /// Lists archived repositories instead, which are shown in the archived state on --long.
+    #[clap(short, long, conflicts_with_all = ["selector", "recent"])]
+    archived: bool,
 }
 
 impl Cmd {
-    pub fn run(self) -> Result<()> {
-        let root = Root::find()?;
+    pub fn run(self, format: &Format) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        if self.archived {
+            return self.print_archived(format, &config, &roots);
+        }
+
+        let selector = Selector::parse(&self.selector)?;
+        let repositories = Repositories::try_collect(&roots)?.select(&selector, &config.rules);
+        let histories = match self.recent {
+            true => Some(Histories::load(&roots)?),
+            _ => None,
+        };
+
+        // Pairs each repository with its last visit, to sort by it on --recent.
+        let last_visit = |path: &Path| match &histories {
+            Some(h) => h.get(path).map(|e| Some(e.last)),
+            _ => Some(None),
+        };
+
+        if format != &Format::Text {
+            let records = repositories
+                .into_iter()
+                .filter_map(|(path, _)| Some((last_visit(&path)?, PathRecord::from(&path))))
+                .sorted_by(|(a, ra), (b, rb)| b.cmp(a).then_with(|| ra.path.cmp(&rb.path)))
+                .map(|(_, r)| r)
+                .collect::<Vec<_>>();
 
-        Repositories::try_collect(&root)?
+            return format.print(&records, |r| r.path.to_string());
+        }
+
+        if self.long {
+            repositories.load_sizes();
+        }
+
+        let rows = repositories
             .into_iter()
-            .map(|(path, _)| match self.path {
-                true => PathBuf::from(path).to_string_lossy().to_string(),
-                _ => path.to_string_with(!self.no_host, !self.no_owner),
+            .filter_map(|(path, repo)| Some((last_visit(&path)?, path, repo)))
+            .map(|(last, path, repo)| {
+                let name = match self.path {
+                    true => PathBuf::from(&path).to_string_lossy().to_string(),
+                    _ => path.to_string_with(!self.no_host, !self.no_owner),
+                };
+
+                let mut row = vec![name];
+                if self.long {
+                    let profile = config
+                        .rules
+                        .resolve(&path.to_url())
+                        .and_then(|r| r.profile.as_ref())
+                        .map(|p| p.name.as_str());
+
+                    row.extend(long_columns(repo.metadata(), profile));
+                }
+
+                (Reverse(last), row)
             })
             .sorted()
-            .for_each(|path| println!("{}", path));
+            .map(|(_, row)| row)
+            .collect::<Vec<_>>();
+
+        print_rows(rows);
+
+        Ok(())
+    }
+
+    fn print_archived(&self, format: &Format, config: &Config, roots: &Roots) -> Result<()> {
+        let archives = Archived::list(roots)?;
+
+        if format != &Format::Text {
+            let records = archives
+                .iter()
+                .map(|a| PathRecord::from(&a.path))
+                .collect::<Vec<_>>();
+
+            return format.print(&records, |r| r.path.to_string());
+        }
+
+        let rows = archives
+            .iter()
+            .map(|a| {
+                let name = match self.path {
+                    true => PathBuf::from(&a.path).to_string_lossy().to_string(),
+                    _ => a.path.to_string_with(!self.no_host, !self.no_owner),
+                };
+
+                let mut row = vec![name];
+                if self.long {
+                    let profile = config
+                        .rules
+                        .resolve(&a.path.to_url())
+                        .and_then(|r| r.profile.as_ref())
+                        .map(|p| p.name.as_str());
+
+                    let mut columns = long_columns(None, profile);
+                    columns[3] = "archived".to_string();
+                    columns[5] = a
+                        .size()
+                        .map(human_size)
+                        .unwrap_or_else(|_| NONE.to_string());
+                    row.extend(columns);
+                }
+
+                row
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+
+        print_rows(rows);
 
         Ok(())
     }
@@ -0,0 +1,125 @@
+use std::env::{split_paths, var_os};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use git2::{Config as GitConfig, Repository};
+use tracing::{info, warn};
+
+use crate::cmd::add::find_url;
+use crate::cmd::import::{Placement, Plan, scan};
+use crate::config::Config;
+use crate::path::expand_home;
+use crate::root::Roots;
+use crate::url::{Host, Url};
+
+/// Repository managers that place repositories as `host/owner/repo` under their roots.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum Source {
+    Ghq,
+    /// `src` directories of GOPATH, used by `go get` before Go modules.
+    Gopath,
+}
+
+impl Source {
+    /// Finds the roots of the manager from its configuration, or the default ones.
+    fn roots(&self) -> Result<Vec<PathBuf>> {
+        Ok(match self {
+            Self::Ghq => {
+                let mut roots = Vec::new();
+                if let Ok(config) = GitConfig::open_default() {
+                    let mut entries = config.multivar("ghq.root", None)?;
+                    while let Some(entry) = entries.next() {
+                        if let Some(value) = entry?.value() {
+                            roots.push(expand_home(value)?);
+                        }
+                    }
+                }
+
+                match roots.is_empty() {
+                    true => vec![expand_home("~/ghq")?],
+                    _ => roots,
+                }
+            }
+            Self::Gopath => match var_os("GOPATH") {
+                Some(paths) => split_paths(&paths).map(|p| p.join("src")).collect(),
+                _ => vec![expand_home("~/go/src")?],
+            },
+        })
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Repository manager to migrate from.
+    #[clap(long, value_enum)]
+    from: Source,
+
+    /// Root directory of the manager.
+    /// Defaults to ghq.root in Git config or ~/ghq for ghq, and $GOPATH/src or ~/go/src for gopath.
+    #[clap(long)]
+    root: Option<String>,
+
+    /// Links to the repositories from the root instead of moving them.
+    #[clap(long)]
+    symlink: bool,
+
+    /// Prints the moves without performing them.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Forces to move the repositories without any prompt.
+    #[clap(short, long)]
+    force: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let excludes = roots.iter().map(|r| r.path().as_path()).collect::<Vec<_>>();
+
+        let sources = match self.root.as_deref() {
+            Some(r) => vec![expand_home(r)?],
+            _ => self.from.roots()?,
+        };
+
+        let mut repos = Vec::new();
+        for source in &sources {
+            if !source.exists() {
+                warn!("Skipped the root not found: {}", source.to_string_lossy());
+                continue;
+            }
+
+            info!("Scanning {}", source.to_string_lossy());
+            repos.extend(scan(source, &excludes)?);
+        }
+
+        let plan = Plan::new(&config, &roots, sources.clone(), repos, |from| {
+            // Reads the host/owner/repo layout first, then falls back to the remotes for
+            // repositories placed deeper, such as subgroups of GitLab.
+            let parts = sources
+                .iter()
+                .find_map(|s| from.strip_prefix(s).ok())
+                .map(|p| p.iter().map(|c| c.to_string_lossy()).collect::<Vec<_>>());
+
+            match parts.as_deref() {
+                Some([host, owner, repo]) => Ok(Url {
+                    host: Host::from_str(host)?,
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    ..Default::default()
+                }),
+                _ => find_url(&config, &Repository::open(from)?, None),
+            }
+        });
+
+        let placement = match self.symlink {
+            true => Placement::Symlink,
+            _ => Placement::Move,
+        };
+
+        plan.run(&config, placement, self.dry_run, self.force)
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use console::style;
+use dialoguer::Confirm;
+use itertools::Itertools;
+use tracing::{info, warn};
+
+use crate::cmd::import::{Placement, transfer};
+use crate::config::Config;
+use crate::index::Index;
+use crate::layout::{DEFAULT_LAYOUT, Layout};
+use crate::path::Path;
+use crate::repository::Repositories;
+use crate::root::Roots;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Layout the repositories are currently placed in.
+    #[clap(long, default_value = DEFAULT_LAYOUT)]
+    from: Layout,
+
+    /// Name of the root to migrate. Defaults to the default root.
+    #[clap(long)]
+    root: Option<String>,
+
+    /// Prints the moves without performing them.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Forces to move the repositories without any prompt.
+    #[clap(short, long)]
+    force: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let root = match self.root.as_deref() {
+            Some(name) => roots.get(name)?,
+            _ => roots.default_root(),
+        };
+
+        let current = root.clone().with_layout(self.from);
+        let moves = Repositories::try_collect_in(&current, &roots.others(root))?
+            .into_iter()
+            .map(|(path, _)| {
+                let to = PathBuf::from(Path::new(root, &path.host, &path.owner, &path.repo));
+
+                (PathBuf::from(path), to)
+            })
+            .filter(|(from, to)| from != to)
+            .sorted()
+            .collect::<Vec<_>>();
+
+        if moves.is_empty() {
+            info!("All repositories are already placed in the layout.");
+            return Ok(());
+        }
+
+        for (from, to) in &moves {
+            info!(
+                "{} -> {}",
+                from.to_string_lossy(),
+                style(to.to_string_lossy()).bold(),
+            );
+        }
+
+        if self.dry_run
+            || !self.force
+                && !Confirm::new()
+                    .with_prompt(format!(
+                        "{} {} repositories will be moved. Are you sure want to continue?",
+                        style("CHECK").dim(),
+                        moves.len(),
+                    ))
+                    .interact()?
+        {
+            return Ok(());
+        }
+
+        let mut moved = 0;
+        for (from, to) in moves {
+            if to.exists() {
+                warn!(
+                    "Skipped moving as the destination already exists: {}",
+                    to.to_string_lossy(),
+                );
+                continue;
+            }
+
+            transfer(&from, &to, Placement::Move)?;
+            roots.prune(&from)?;
+            moved += 1;
+        }
+
+        if Index::exists(root) {
+            Index::rebuild(&roots, root)?;
+        }
+
+        info!(
+            "Moved {} repositories to the new layout successfully.",
+            moved
+        );
+
+        Ok(())
+    }
+}
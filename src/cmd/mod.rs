@@ -5,49 +5,96 @@ use clap::{Parser, Subcommand};
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::filter::LevelFilter;
 
+use crate::format::Format;
+
 mod add;
+mod archive;
 mod browse;
 mod cd;
 mod clone;
+mod config;
 mod delete;
+mod exec;
+mod fetch;
+mod gc;
+mod import;
 mod init;
 mod list;
+mod migrate;
+mod migrate_layout;
 mod open;
 mod path;
 mod profile;
+mod reindex;
+mod relocate;
+mod rename;
 mod search;
 mod shell;
+mod status;
 mod sync;
+mod trash;
+mod unarchive;
+mod verify;
 mod version;
 
 #[derive(Debug, Subcommand)]
 pub enum Action {
     /// Add an existing repository into the ghr managed directory.
     Add(add::Cmd),
+    /// Packs repositories into archives under the root, removing their working directories.
+    Archive(archive::Cmd),
     /// Browse a repository on web.
     Browse(browse::Cmd),
     /// Change directory to one of the managed repositories (Shell extension required).
     Cd(cd::Cmd),
     /// Clones a Git repository to local.
     Clone(clone::Cmd),
+    /// Manages configuration repositories shared in your team.
+    Config(config::Cmd),
     /// Deletes a repository from local.
     Delete(delete::Cmd),
+    /// Runs a command in each of the selected repositories.
+    Exec(exec::Cmd),
+    /// Fetches all remotes of the selected repositories.
+    Fetch(fetch::Cmd),
+    /// Removes empty directories and moves non-repository directories in the roots into the trash.
+    Gc(gc::Cmd),
+    /// Moves all repositories found in a directory into the ghr managed directory.
+    Import(import::Cmd),
     /// Initialises a Git repository in local.
     Init(init::Cmd),
     /// Lists all managed repositories.
     List(list::Cmd),
+    /// Moves repositories managed by ghq or placed in GOPATH into the ghr managed directory.
+    Migrate(migrate::Cmd),
+    /// Moves repositories into the layout configured currently.
+    MigrateLayout(migrate_layout::Cmd),
     /// Opens a repository in an application.
     Open(open::Cmd),
     /// Prints the path to root, owner, or a repository.
     Path(path::Cmd),
     /// Manages profiles to use in repositories.
     Profile(profile::Cmd),
+    /// Rebuilds the index of repositories to speed up listing.
+    Reindex(reindex::Cmd),
+    /// Moves repositories renamed or transferred on the platform to their new location.
+    Relocate(relocate::Cmd),
+    /// Renames a repository on the platform, then relocates the local checkout.
+    Rename(rename::Cmd),
     /// Perform a fuzzy search on the repositories list.
     Search(search::Cmd),
     /// Writes a shell script to extend ghr features.
     Shell(shell::Cmd),
+    /// Prints the branch and state of the selected repositories.
+    Status(status::Cmd),
     /// Sync repositories between your devices.
     Sync(sync::Cmd),
+    /// Lists, restores or empties the repositories deleted into the trash.
+    Trash(trash::Cmd),
+    /// Restores archived repositories into their original location.
+    Unarchive(unarchive::Cmd),
+    /// Checks that repositories are placed at the location of their remotes.
+    Verify(verify::Cmd),
     /// Prints the version of this application.
     Version(version::Cmd),
 }
@@ -57,6 +104,12 @@ pub struct Cli {
     #[clap(subcommand)]
     action: Action,
 
+    /// Output format of list, search, path, profile and sync commands.
+    /// One of text, json, tsv, or a template such as '{{owner}}/{{repo}}'.
+    /// sync dump and sync import also accept manifests of vcstool, repo and mr.
+    #[clap(long, global = true, default_value_t)]
+    format: Format,
+
     /// Operates quietly. Errors will be reported even if this option is enabled.
     #[clap(short, long, global = true)]
     quiet: bool,
@@ -87,18 +140,33 @@ impl Cli {
         use Action::*;
         match self.action {
             Add(cmd) => cmd.run(),
+            Archive(cmd) => cmd.run().await,
             Cd(cmd) => cmd.run(),
             Clone(cmd) => cmd.run().await,
+            Config(cmd) => cmd.run().await,
             Delete(cmd) => cmd.run().await,
+            Exec(cmd) => cmd.run(),
+            Fetch(cmd) => cmd.run().await,
+            Gc(cmd) => cmd.run(),
+            Import(cmd) => cmd.run(),
             Init(cmd) => cmd.run(),
-            List(cmd) => cmd.run(),
+            List(cmd) => cmd.run(&self.format),
+            Migrate(cmd) => cmd.run(),
+            MigrateLayout(cmd) => cmd.run(),
             Open(cmd) => cmd.run(),
             Browse(cmd) => cmd.run().await,
-            Path(cmd) => cmd.run(),
-            Profile(cmd) => cmd.run(),
-            Search(cmd) => cmd.run(),
+            Path(cmd) => cmd.run(&self.format),
+            Profile(cmd) => cmd.run(&self.format),
+            Reindex(cmd) => cmd.run().await,
+            Relocate(cmd) => cmd.run().await,
+            Rename(cmd) => cmd.run().await,
+            Search(cmd) => cmd.run(&self.format),
             Shell(cmd) => cmd.run(),
-            Sync(cmd) => cmd.run().await,
+            Status(cmd) => cmd.run(),
+            Sync(cmd) => cmd.run(&self.format).await,
+            Trash(cmd) => cmd.run(),
+            Unarchive(cmd) => cmd.run().await,
+            Verify(cmd) => cmd.run(),
             Version(cmd) => cmd.run(),
         }
     }
@@ -4,8 +4,9 @@ use anyhow::Result;
 use clap::Parser;
 
 use crate::config::Config;
+use crate::history::History;
 use crate::path::Path;
-use crate::root::Root;
+use crate::root::Roots;
 use crate::url::Url;
 
 #[derive(Debug, Parser)]
@@ -19,20 +20,23 @@ pub struct Cmd {
 
 impl Cmd {
     pub fn run(self) -> Result<()> {
-        let root = Root::find()?;
-        let config = Config::load_from(&root)?;
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
 
         let url = Url::from_str(
             &self.repo,
             &config.patterns,
             config.defaults.owner.as_deref(),
         )?;
-        let path = PathBuf::from(Path::resolve(&root, &url));
+        let target = Path::resolve(roots.resolve(&url, &config.rules)?, &url);
+        let path = PathBuf::from(&target);
 
         config
             .applications
             .open_or_intermediate_or_default(self.application.as_deref(), path)?;
 
+        History::record_or_warn(&target);
+
         Ok(())
     }
 }
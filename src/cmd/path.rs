@@ -4,8 +4,10 @@ use anyhow::{Result, anyhow};
 use clap::Parser;
 
 use crate::config::Config;
+use crate::format::{Format, PathRecord};
+use crate::history::History;
 use crate::path::{PartialPath, Path};
-use crate::root::Root;
+use crate::root::Roots;
 use crate::url::{Host, Url};
 
 #[derive(Debug, Parser)]
@@ -14,6 +16,10 @@ pub struct Cmd {
     #[clap(short, long)]
     relative: bool,
 
+    /// Name of the root to use instead of the one chosen by the rules.
+    #[clap(long)]
+    root: Option<String>,
+
     /// Remote host of the repository.
     /// Defaults to github.com.
     #[clap(long)]
@@ -29,44 +35,64 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(self) -> Result<()> {
-        let root = Root::find()?;
-        let config = Config::load_from(&root)?;
+    pub fn run(self, format: &Format) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
 
-        let path = if let Some(repo) = self.repo.as_deref() {
+        let (root, target, mut record) = if let Some(repo) = self.repo.as_deref() {
             let url = Url::from_str(
                 repo,
                 &config.patterns,
                 self.owner.as_deref().or(config.defaults.owner.as_deref()),
             )?;
 
-            PathBuf::from(Path::resolve(&root, &url))
+            let root = match self.root.as_deref() {
+                Some(name) => roots.get(name)?,
+                _ => roots.resolve(&url, &config.rules)?,
+            };
+
+            let target = Path::resolve(root, &url);
+            let record = PathRecord::from(&target);
+
+            (root, Some(target), record)
         } else {
-            PathBuf::from(PartialPath {
-                root: &root,
+            let root = match self.root.as_deref() {
+                Some(name) => roots.get(name)?,
+                _ => roots.default_root(),
+            };
+
+            let partial = PartialPath {
+                root,
                 host: match self.owner.is_some() || self.repo.is_some() {
                     true => self.host.or_else(|| Some(Host::GitHub.to_string())),
                     _ => self.host,
                 },
                 owner: self.owner,
                 repo: None,
-            })
+            };
+
+            (root, None, PathRecord::from(&partial))
         };
 
+        let path = PathBuf::from(&record.path);
         if !path.exists() || !path.is_dir() {
             return Err(anyhow!(
                 "The path does not exist or is not a directory. Did you cloned the repository?"
             ));
         }
 
-        println!(
-            "{}",
-            match self.relative {
-                true => path.strip_prefix(root.path())?.to_string_lossy(),
-                _ => path.to_string_lossy(),
-            },
-        );
+        // The shell extension resolves the directory to change into with this command.
+        if let Some(target) = &target {
+            History::record_or_warn(target);
+        }
+
+        if self.relative {
+            record.path = path
+                .strip_prefix(root.path())?
+                .to_string_lossy()
+                .to_string();
+        }
 
-        Ok(())
+        format.print_one(&record, |r| r.path.to_string())
     }
 }
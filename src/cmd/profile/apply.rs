@@ -21,7 +21,7 @@ impl Cmd {
             .ok_or_else(|| anyhow!("Unknown profile: {}", &self.name))?;
 
         let repo = Repository::open_from_env()?;
-        profile.apply(&repo)?;
+        profile.apply(&self.name, &repo)?;
 
         info!(
             "Attached profile [{}] successfully.",
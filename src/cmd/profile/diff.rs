@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use console::style;
+use git2::Repository;
+use tracing::info;
+
+use crate::cmd::profile::origin_url;
+use crate::config::Config;
+use crate::path::Path;
+use crate::profile::Applied;
+use crate::root::Roots;
+use crate::url::Url;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// URL or pattern of the repository to compare. Defaults to the current directory.
+    repo: Option<String>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let (repo, url) = match &self.repo {
+            Some(r) => {
+                let roots = Roots::find(&config)?;
+                let url = Url::from_str(r, &config.patterns, config.defaults.owner.as_deref())?;
+                let path = Path::resolve(roots.resolve(&url, &config.rules)?, &url);
+
+                (Repository::open(PathBuf::from(&path))?, Some(url))
+            }
+            _ => (Repository::open_from_env()?, None),
+        };
+
+        let url = url.or_else(|| origin_url(&repo, &config));
+
+        // Prefers the profile the rules resolve to, then the one applied previously.
+        let name = match url
+            .as_ref()
+            .and_then(|u| config.rules.resolve(u))
+            .and_then(|r| r.profile.as_ref())
+        {
+            Some(p) => p.name.clone(),
+            _ => Applied::load(&repo)?
+                .map(|a| a.profile)
+                .ok_or_else(|| anyhow!("No profile is configured for the repository."))?,
+        };
+
+        let profile = config
+            .profiles
+            .get(&name)
+            .ok_or_else(|| anyhow!("Unknown profile: {}", &name))?;
+
+        let drifts = profile.diff(&repo)?;
+        if drifts.is_empty() {
+            info!(
+                "The repository is up to date with profile [{}].",
+                style(&name).bold()
+            );
+            return Ok(());
+        }
+
+        for drift in drifts {
+            print!("{}", drift);
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
 use anyhow::Result;
 use clap::Parser;
 use console::style;
+use itertools::Itertools;
 
+use crate::cmd::profile::ProfileRecord;
 use crate::config::Config;
+use crate::format::Format;
 
 const INHERIT: &str = "(inherit)";
 
@@ -14,9 +17,20 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(self) -> Result<()> {
+    pub fn run(self, format: &Format) -> Result<()> {
         let config = Config::load()?;
 
+        if format != &Format::Text {
+            let records = config
+                .profiles
+                .iter()
+                .sorted_by_key(|(name, _)| name.to_string())
+                .map(|(name, profile)| ProfileRecord::new(name, profile))
+                .collect::<Vec<_>>();
+
+            return format.print(&records, |r| r.name.to_string());
+        }
+
         config.profiles.iter().for_each(|(name, profile)| {
             if self.short {
                 println!("{}", name)
@@ -28,15 +42,15 @@ impl Cmd {
                     profile
                         .configs
                         .get("user.name")
-                        .map(|name| name.as_str())
-                        .unwrap_or(INHERIT),
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| INHERIT.to_string()),
                     style(&format!(
                         "<{}>",
                         profile
                             .configs
                             .get("user.email")
-                            .map(|email| email.as_str())
-                            .unwrap_or(INHERIT),
+                            .map(|email| email.to_string())
+                            .unwrap_or_else(|| INHERIT.to_string()),
                     ))
                     .dim(),
                 );
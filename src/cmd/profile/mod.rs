@@ -1,9 +1,29 @@
 mod apply;
+mod diff;
 mod list;
 mod show;
+mod sync;
+mod unapply;
+
+use std::collections::BTreeMap;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use git2::Repository;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::format::{Format, Record};
+use crate::profile::{ConfigValue, Hook, Profile};
+use crate::url::Url;
+
+/// Parses URL of the `origin` remote, which identifies the repository regardless of the layout.
+fn origin_url(repo: &Repository, config: &Config) -> Option<Url> {
+    repo.find_remote("origin")
+        .ok()?
+        .url()
+        .and_then(|u| Url::from_str(u, &config.patterns, None).ok())
+}
 
 #[derive(Debug, Subcommand)]
 pub enum Action {
@@ -13,6 +33,12 @@ pub enum Action {
     Show(show::Cmd),
     /// Apply a profile.
     Apply(apply::Cmd),
+    /// Reverts the values written by the profile applied previously.
+    Unapply(unapply::Cmd),
+    /// Shows differences between a repository and its profile.
+    Diff(diff::Cmd),
+    /// Applies the profiles resolved by the rules to all repositories drifted from them.
+    Sync(sync::Cmd),
 }
 
 #[derive(Debug, Parser)]
@@ -22,12 +48,83 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(self) -> Result<()> {
+    pub fn run(self, format: &Format) -> Result<()> {
         use Action::*;
         match self.action {
-            List(cmd) => cmd.run(),
-            Show(cmd) => cmd.run(),
+            List(cmd) => cmd.run(format),
+            Show(cmd) => cmd.run(format),
             Apply(cmd) => cmd.run(),
+            Unapply(cmd) => cmd.run(),
+            Diff(cmd) => cmd.run(),
+            Sync(cmd) => cmd.run(),
         }
     }
 }
+
+#[derive(Debug, Serialize)]
+struct ProfileRecord {
+    name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    base_profiles: Vec<String>,
+    configs: BTreeMap<String, ConfigValue>,
+    excludes: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    git_hooks: BTreeMap<String, Hook>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    git_attributes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hooks_path: Option<String>,
+}
+
+impl ProfileRecord {
+    fn new(name: &str, profile: &Profile) -> Self {
+        Self {
+            name: name.to_string(),
+            base_profiles: profile.extends.clone(),
+            configs: profile
+                .configs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+            excludes: profile.excludes.clone(),
+            git_hooks: profile.hooks.clone(),
+            git_attributes: profile.attributes.clone(),
+            hooks_path: profile.hooks_path.clone(),
+        }
+    }
+}
+
+impl Record for ProfileRecord {
+    fn fields(&self) -> Vec<(String, String)> {
+        [
+            ("name".to_string(), self.name.to_string()),
+            ("base_profiles".to_string(), self.base_profiles.join(",")),
+            ("excludes".to_string(), self.excludes.join(",")),
+            ("git_attributes".to_string(), self.git_attributes.join(",")),
+            (
+                "hooks_path".to_string(),
+                self.hooks_path.clone().unwrap_or_default(),
+            ),
+        ]
+        .into_iter()
+        .chain(
+            self.git_hooks
+                .iter()
+                .map(|(k, v)| (format!("git_hooks.{}", k), v.to_string())),
+        )
+        .chain(
+            self.configs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string())),
+        )
+        .collect()
+    }
+
+    /// Prints a row of the profile name, key and value for each config.
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.configs
+            .iter()
+            .map(|(k, v)| vec![self.name.to_string(), k.to_string(), v.to_string()])
+            .collect()
+    }
+}
@@ -1,29 +1,61 @@
 use anyhow::{Result, anyhow};
 use clap::Parser;
 use itertools::Itertools;
+use toml::Value;
 
+use crate::cmd::profile::ProfileRecord;
 use crate::config::Config;
+use crate::format::Format;
 
 #[derive(Debug, Parser)]
 pub struct Cmd {
     // Name of the profile to show.
     name: String,
+
+    /// Shows the profile merged with the ones it extends, instead of as declared.
+    #[clap(long)]
+    resolved: bool,
 }
 
 impl Cmd {
-    pub fn run(self) -> Result<()> {
+    pub fn run(self, format: &Format) -> Result<()> {
         let config = Config::load()?;
-        let profile = config
-            .profiles
-            .get(&self.name)
-            .ok_or_else(|| anyhow!("Unknown profile: {}", &self.name))?;
+        let profile = match self.resolved {
+            true => config.profiles.get(&self.name),
+            _ => config.profiles.declared(&self.name),
+        }
+        .ok_or_else(|| anyhow!("Unknown profile: {}", &self.name))?;
+
+        if format != &Format::Text {
+            return format.print_one(&ProfileRecord::new(&self.name, profile), |r| {
+                r.name.to_string()
+            });
+        }
+
+        for (key, values) in [
+            ("base_profiles", &profile.extends),
+            ("excludes", &profile.excludes),
+            ("git_attributes", &profile.attributes),
+        ] {
+            if !values.is_empty() {
+                println!("{} = {}", key, Value::from(values.clone()));
+            }
+        }
+
+        if let Some(path) = &profile.hooks_path {
+            println!("hooks_path = {}", Value::from(path.clone()));
+        }
+
+        for (name, hook) in &profile.hooks {
+            println!("git_hooks.{} = {}", name, Value::try_from(hook)?);
+        }
 
         profile
             .configs
             .iter()
             .sorted_by_key(|(k, _)| k.to_string())
             .for_each(|(k, v)| {
-                println!(r#"{} = "{}""#, k, v);
+                println!("{} = {}", k, v.to_toml());
             });
 
         Ok(())
@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use console::style;
+use git2::Repository;
+use itertools::Itertools;
+use tracing::{info, warn};
+
+use crate::cmd::profile::origin_url;
+use crate::config::Config;
+use crate::profile::{Applied, Drift, Profile};
+use crate::repository::Repositories;
+use crate::root::Roots;
+use crate::selector::Selector;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Terms to filter repositories, such as `owner:acme profile:work`.
+    selector: Vec<String>,
+
+    /// Prints the differences without applying the profiles.
+    #[clap(long)]
+    dry_run: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let selector = Selector::parse(&self.selector)?;
+
+        let repositories = Repositories::try_collect(&roots)?.select(&selector, &config.rules);
+        let targets = repositories
+            .into_iter()
+            .filter_map(|(path, _)| {
+                // Hosts are not known from the path in layouts without them.
+                let url = Repository::open(PathBuf::from(&path))
+                    .ok()
+                    .and_then(|r| origin_url(&r, &config))
+                    .unwrap_or_else(|| path.to_url());
+
+                config
+                    .rules
+                    .resolve(&url)
+                    .and_then(|r| r.profile.as_ref())
+                    .and_then(|p| config.profiles.resolve(p))
+                    .map(|(name, profile)| (path, name, profile))
+            })
+            .sorted_by_key(|(path, _, _)| path.to_string())
+            .collect::<Vec<_>>();
+
+        let mut changed = 0;
+        for (path, name, profile) in targets {
+            let result = Repository::open(PathBuf::from(&path))
+                .map_err(anyhow::Error::from)
+                .and_then(|repo| {
+                    let Some(drifts) = drifts(name, profile, &repo)? else {
+                        return Ok(false);
+                    };
+
+                    info!(
+                        "{} profile [{}] to {}",
+                        match self.dry_run {
+                            true => "Would apply",
+                            _ => "Applying",
+                        },
+                        style(name).bold(),
+                        path,
+                    );
+                    for drift in drifts {
+                        print!("{}", drift);
+                    }
+
+                    if !self.dry_run {
+                        profile.apply(name, &repo)?;
+                    }
+
+                    Ok(true)
+                });
+
+            match result {
+                Ok(true) => changed += 1,
+                Ok(false) => {}
+                Err(e) => warn!("Could not apply profile [{}] to {}: {}", name, path, e),
+            }
+        }
+
+        match (changed, self.dry_run) {
+            (0, _) => info!("All repositories are up to date with their profiles."),
+            (_, true) => info!("{} repositories would be changed.", changed),
+            _ => info!("Applied profiles to {} repositories successfully.", changed),
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the differences from the profile, or `None` if the repository is up to date.
+/// Switching from another profile applied previously is a change even without differences.
+fn drifts(name: &str, profile: &Profile, repo: &Repository) -> Result<Option<Vec<Drift>>> {
+    let drifts = profile.diff(repo)?;
+    let switched = Applied::load(repo)?.is_some_and(|a| a.profile != name);
+
+    Ok((!drifts.is_empty() || switched).then_some(drifts))
+}
@@ -0,0 +1,25 @@
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use console::style;
+use git2::Repository;
+use tracing::info;
+
+use crate::profile::Applied;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let repo = Repository::open_from_env()?;
+        let applied = Applied::load(&repo)?
+            .ok_or_else(|| anyhow!("No profile is applied to the repository."))?;
+
+        let name = applied.profile.clone();
+        applied.revert(&repo)?;
+
+        info!("Detached profile [{}] successfully.", style(name).bold());
+
+        Ok(())
+    }
+}
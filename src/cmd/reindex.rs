@@ -0,0 +1,41 @@
+use anyhow::Result;
+use clap::Parser;
+use tracing::info;
+
+use crate::config::Config;
+use crate::console::Spinner;
+use crate::index::Index;
+use crate::root::Roots;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Name of the root to reindex. Defaults to all the roots.
+    #[clap(long)]
+    root: Option<String>,
+}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let targets = match self.root.as_deref() {
+            Some(name) => vec![roots.get(name)?],
+            _ => roots.iter().collect(),
+        };
+
+        for root in targets {
+            let count = Spinner::new("Indexing the repositories...")
+                .spin_while(|| async { Index::rebuild(&roots, root) })
+                .await?;
+
+            info!(
+                "Indexed {} repositories in: {}",
+                count,
+                root.path().to_string_lossy(),
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow, bail};
+use clap::Parser;
+use console::style;
+use git2::Repository;
+use itertools::Itertools;
+use tracing::{debug, info, warn};
+
+use crate::cmd::import::{Placement, transfer};
+use crate::config::Config;
+use crate::console::Spinner;
+use crate::index::Index;
+use crate::path::Path;
+use crate::platform::Platform;
+use crate::repository::Repositories;
+use crate::root::Roots;
+use crate::url::Url;
+
+/// Moves the checkout to the location of the new URL and rewrites its remotes pointing to the
+/// old one. Returns the new path, or `None` if the checkout is already in place.
+pub(super) fn relocate<'a>(
+    config: &Config,
+    roots: &'a Roots,
+    from: &Path,
+    to: &Url,
+    dry_run: bool,
+) -> Result<Option<Path<'a>>> {
+    let target = Path::resolve(roots.resolve(to, &config.rules)?, to);
+    let (src, dst) = (PathBuf::from(from), PathBuf::from(&target));
+    if src == dst {
+        return Ok(None);
+    }
+
+    info!(
+        "{} -> {}",
+        src.to_string_lossy(),
+        style(dst.to_string_lossy()).bold(),
+    );
+
+    if dst.exists() {
+        bail!("The destination already exists: {}", dst.to_string_lossy());
+    }
+
+    if dry_run {
+        return Ok(Some(target));
+    }
+
+    transfer(&src, &dst, Placement::Move)?;
+    roots.prune(&src)?;
+
+    Index::remove(from)?;
+    Index::insert(&target)?;
+
+    let repo = Repository::open(&dst)?;
+    for name in repo.remotes()?.iter().flatten() {
+        let remote = repo.find_remote(name)?;
+        if let Some(url) = remote.url().and_then(|u| rewrite_url(config, u, from, to)) {
+            repo.remote_set_url(name, &url)?;
+            info!("Rewrote the URL of remote {}: {}", name, url);
+        }
+
+        if let Some(url) = remote
+            .pushurl()
+            .and_then(|u| rewrite_url(config, u, from, to))
+        {
+            repo.remote_set_pushurl(name, Some(&url))?;
+        }
+    }
+
+    Ok(Some(target))
+}
+
+/// Rewrites the remote URL if it points to the old location, keeping its scheme and form.
+fn rewrite_url(config: &Config, url: &str, from: &Path, to: &Url) -> Option<String> {
+    let parsed = Url::from_str(url, &config.patterns, None).ok()?;
+    let matches = parsed.host.to_string() == from.host
+        && parsed.owner.eq_ignore_ascii_case(&from.owner)
+        && parsed.repo.eq_ignore_ascii_case(&from.repo);
+
+    if !matches {
+        return None;
+    }
+
+    let old = format!("{}/{}", parsed.owner, parsed.repo);
+    let new = format!("{}/{}", to.owner, to.repo);
+    match url.contains(&old) {
+        true => Some(url.replacen(&old, &new, 1)),
+        _ => Some(
+            Url {
+                owner: to.owner.to_string(),
+                repo: to.repo.to_string(),
+                raw: None,
+                ..parsed
+            }
+            .to_string(),
+        ),
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// URL or pattern of the repository to relocate.
+    #[clap(required_unless_present = "all")]
+    repo: Option<String>,
+
+    /// Relocates all repositories hosted on the configured platforms.
+    #[clap(long, conflicts_with = "repo")]
+    all: bool,
+
+    /// Prints the moves without performing them.
+    #[clap(long)]
+    dry_run: bool,
+}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let paths = match self.repo.as_deref() {
+            Some(repo) => {
+                let url = Url::from_str(repo, &config.patterns, config.defaults.owner.as_deref())?;
+                let path = Path::resolve(roots.resolve(&url, &config.rules)?, &url);
+                if !PathBuf::from(&path).exists() {
+                    bail!("The repository is not cloned: {}", path);
+                }
+
+                vec![path]
+            }
+            _ => Repositories::try_collect(&roots)?
+                .into_iter()
+                .map(|(p, _)| p)
+                .sorted_by_key(|p| p.to_string())
+                .collect(),
+        };
+
+        let mut platforms = HashMap::<String, Box<dyn Platform>>::new();
+        let mut relocated = 0;
+        for path in paths {
+            let url = path.to_url();
+            let Some(platform_config) = config.platforms.find(&url) else {
+                match self.all {
+                    true => debug!("Skipped a repository without platform: {}", path),
+                    _ => warn!("Could not find a platform hosting the repository: {}", path),
+                }
+                continue;
+            };
+
+            if !platforms.contains_key(&path.host) {
+                platforms.insert(path.host.to_string(), platform_config.try_into_platform()?);
+            }
+
+            let platform = &platforms[&path.host];
+
+            let result = Spinner::new(format!("Locating {}...", path))
+                .spin_while(|| platform.locate(&url))
+                .await
+                .map_err(|e| anyhow!("Could not locate the repository {}: {}", path, e))
+                .and_then(|to| relocate(&config, &roots, &path, &to, self.dry_run));
+
+            match result {
+                Ok(Some(_)) => relocated += 1,
+                Ok(None) => debug!("The repository is already in place: {}", path),
+                Err(e) => warn!("{}", e),
+            }
+        }
+
+        match (relocated, self.dry_run) {
+            (0, _) => info!("All repositories are already in place."),
+            (n, true) => info!("{} repositories would be relocated.", n),
+            (n, _) => info!("Relocated {} repositories successfully.", n),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use console::style;
+use dialoguer::Confirm;
+use tracing::info;
+
+use crate::cmd::relocate::relocate;
+use crate::config::Config;
+use crate::console::Spinner;
+use crate::path::Path;
+use crate::root::Roots;
+use crate::url::Url;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// URL or pattern of the repository to rename.
+    repo: String,
+
+    /// New name of the repository.
+    name: String,
+}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let url = Url::from_str(
+            &self.repo,
+            &config.patterns,
+            config.defaults.owner.as_deref(),
+        )?;
+
+        let platform = config
+            .platforms
+            .find(&url)
+            .ok_or_else(|| anyhow!("Could not find a platform to rename the repository on."))?
+            .try_into_platform()?;
+
+        if !Confirm::new()
+            .with_prompt(format!(
+                "{} {}/{} will be renamed to {}/{} on {}. Are you sure want to continue?",
+                style("CHECK").dim(),
+                url.owner,
+                url.repo,
+                url.owner,
+                self.name,
+                url.host,
+            ))
+            .interact()?
+        {
+            return Ok(());
+        }
+
+        let renamed = Spinner::new("Renaming the repository...")
+            .spin_while(|| platform.rename(&url, &self.name))
+            .await?;
+
+        info!(
+            "Renamed the repository successfully: {}/{}",
+            renamed.owner, renamed.repo,
+        );
+
+        let from = Path::resolve(roots.resolve(&url, &config.rules)?, &url);
+        if PathBuf::from(&from).exists() {
+            relocate(&config, &roots, &from, &renamed, false)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,43 +1,75 @@
 use anyhow::Result;
+use chrono::Utc;
 use clap::Parser;
 use itertools::Itertools;
 use nucleo_matcher::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
-use nucleo_matcher::{Config, Matcher};
+use nucleo_matcher::{Config as MatcherConfig, Matcher, Utf32Str};
 
+use crate::config::Config;
+use crate::format::{Format, PathRecord};
+use crate::history::Histories;
 use crate::repository::Repositories;
-use crate::root::Root;
+use crate::root::Roots;
+use crate::selector::Selector;
 
 const MIN_SCORE: u32 = 50;
 
+// Score added per unit of frecency, so that frequently visited repositories rank first among
+// similarly matching ones.
+const FRECENCY_BOOST: f64 = 4.0;
+const MAX_BOOST: u32 = 100;
+
 #[derive(Debug, Parser)]
 pub struct Cmd {
+    /// Fuzzy query, optionally with selector terms such as `owner:acme` to filter repositories.
     query: String,
 }
 
 impl Cmd {
-    pub fn run(self) -> Result<()> {
-        let root = Root::find()?;
+    pub fn run(self, format: &Format) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let (selector, query) = Selector::split(&self.query)?;
 
-        let mut matcher = Matcher::new(Config::DEFAULT);
+        let mut matcher = Matcher::new(MatcherConfig::DEFAULT);
         let pattern = Pattern::new(
-            &self.query,
+            &query,
             CaseMatching::Smart,
             Normalization::Smart,
             AtomKind::Fuzzy,
         );
 
-        let matches = pattern.match_list(
-            Repositories::try_collect(&root)?
-                .into_iter()
-                .map(|(path, _)| path.to_string()),
-            &mut matcher,
-        );
+        let histories = Histories::load(&roots)?;
+        let now = Utc::now();
 
-        matches
+        let mut buf = Vec::new();
+        let (names, records): (Vec<_>, Vec<_>) = Repositories::try_collect(&roots)?
+            .select(&selector, &config.rules)
             .into_iter()
-            .filter(|(_, score)| *score > MIN_SCORE)
-            .sorted_by_key(|(_, score)| -i64::from(*score))
-            .for_each(|(path, _)| println!("{}", path));
+            .filter_map(|(path, _)| {
+                let name = path.to_string();
+                let score = pattern.score(Utf32Str::new(&name, &mut buf), &mut matcher)?;
+
+                // Lists all the selected repositories if only selector terms are given.
+                if !query.is_empty() && score <= MIN_SCORE {
+                    return None;
+                }
+
+                let boost = histories
+                    .get(&path)
+                    .map(|e| ((e.frecency(now) * FRECENCY_BOOST) as u32).min(MAX_BOOST))
+                    .unwrap_or_default();
+
+                Some((name, PathRecord::from(&path).with_score(score + boost)))
+            })
+            .sorted_by(|(a, ra), (b, rb)| rb.score.cmp(&ra.score).then_with(|| a.cmp(b)))
+            .unzip();
+
+        match format {
+            Format::Text => names.iter().for_each(|name| println!("{}", name)),
+            _ => format.print(&records, |r| r.path.to_string())?,
+        }
 
         Ok(())
     }
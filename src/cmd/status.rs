@@ -0,0 +1,51 @@
+use anyhow::Result;
+use clap::Parser;
+use itertools::Itertools;
+
+use crate::config::Config;
+use crate::console::{NONE, print_rows};
+use crate::repository::Repositories;
+use crate::root::Roots;
+use crate::selector::Selector;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Terms to filter repositories, such as `owner:acme dirty:true`.
+    selector: Vec<String>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let selector = Selector::parse(&self.selector)?;
+
+        let repositories = Repositories::try_collect(&roots)?.select(&selector, &config.rules);
+        repositories.load_metadata();
+
+        let rows = repositories
+            .into_iter()
+            .map(|(path, repo)| {
+                let metadata = repo.metadata();
+                vec![
+                    path.to_string(),
+                    metadata
+                        .and_then(|m| m.branch.as_deref())
+                        .unwrap_or(NONE)
+                        .to_string(),
+                    match metadata.map(|m| m.dirty) {
+                        Some(true) => "dirty",
+                        Some(false) => "clean",
+                        _ => NONE,
+                    }
+                    .to_string(),
+                ]
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+
+        print_rows(rows);
+
+        Ok(())
+    }
+}
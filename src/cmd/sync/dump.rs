@@ -1,24 +1,38 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use clap::Parser;
 use itertools::Itertools;
 
+use crate::config::Config;
+use crate::format::Format;
 use crate::repository::Repositories;
-use crate::root::Root;
+use crate::root::Roots;
+use crate::selector::Selector;
 use crate::sync::File;
 
 #[derive(Debug, Parser)]
-pub struct Cmd {}
+pub struct Cmd {
+    /// Terms to filter repositories to dump, such as `owner:acme`.
+    selector: Vec<String>,
+}
 
 impl Cmd {
-    pub fn run(self) -> Result<()> {
-        let root = Root::find()?;
-        let file = Repositories::try_collect(&root)?
+    pub fn run(self, format: &Format) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let selector = Selector::parse(&self.selector)?;
+        let file = Repositories::try_collect(&roots)?
+            .select(&selector, &config.rules)
             .into_iter()
             .map(|(p, _)| p)
             .sorted_by_key(|p| p.to_string())
             .collect::<File>();
 
-        println!("{}", toml::to_string(&file)?);
+        match format {
+            Format::Text => println!("{}", toml::to_string(&file)?),
+            Format::Json => println!("{}", serde_json::to_string_pretty(&file)?),
+            Format::Manifest(k) => print!("{}", k.dump(&file.to_projects())?),
+            _ => bail!("The format {} is not supported by this command", format),
+        }
 
         Ok(())
     }
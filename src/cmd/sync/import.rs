@@ -0,0 +1,32 @@
+use std::io::{read_to_string, stdin};
+
+use anyhow::{Result, bail};
+use clap::Parser;
+
+use crate::cmd::sync::restore::restore;
+use crate::config::Config;
+use crate::format::Format;
+use crate::root::Roots;
+use crate::sync::File;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {}
+
+impl Cmd {
+    pub async fn run(self, format: &Format) -> Result<()> {
+        let Format::Manifest(kind) = format else {
+            bail!("Specify the format of the manifest by --format: vcstool, repo or mr");
+        };
+
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let projects = kind.parse(&read_to_string(stdin())?)?;
+
+        restore(
+            &config,
+            &roots,
+            File::from_projects(projects, &config.patterns),
+        )
+        .await
+    }
+}
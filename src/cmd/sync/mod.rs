@@ -1,13 +1,18 @@
 mod dump;
+mod import;
 mod restore;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 
+use crate::format::Format;
+
 #[derive(Debug, Subcommand)]
 pub enum Action {
     /// Dump remotes and the current ref of all repositories.
     Dump(dump::Cmd),
+    /// Clone repositories from a manifest of vcstool, repo or mr.
+    Import(import::Cmd),
     /// Restore repositories from the dumped file.
     Restore(restore::Cmd),
 }
@@ -19,10 +24,11 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub async fn run(self) -> Result<()> {
+    pub async fn run(self, format: &Format) -> Result<()> {
         use Action::*;
         match self.action {
-            Dump(cmd) => cmd.run(),
+            Dump(cmd) => cmd.run(format),
+            Import(cmd) => cmd.run(format).await,
             Restore(cmd) => cmd.run().await,
         }
     }
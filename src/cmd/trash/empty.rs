@@ -0,0 +1,71 @@
+use anyhow::Result;
+use chrono::Utc;
+use clap::Parser;
+use console::style;
+use dialoguer::Confirm;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::root::Roots;
+use crate::selector::parse_duration;
+use crate::trash::Trashed;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Deletes only the repositories deleted before the duration, such as `30d` (h, d, w, m or y).
+    #[clap(long)]
+    older_than: Option<String>,
+
+    /// Forces to empty the trash without any prompt.
+    #[clap(short, long)]
+    force: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let threshold = self
+            .older_than
+            .as_deref()
+            .map(parse_duration)
+            .transpose()?
+            .map(|d| Utc::now() - d);
+
+        let targets = Trashed::list(&roots)?
+            .into_iter()
+            .filter(|t| threshold.map(|th| t.deleted_at < th).unwrap_or(true))
+            .collect::<Vec<_>>();
+
+        if targets.is_empty() {
+            info!("No repositories to delete in the trash.");
+            return Ok(());
+        }
+
+        if !self.force
+            && !Confirm::new()
+                .with_prompt(format!(
+                    "{} {} repositories in the trash will be deleted permanently. Are you sure want to continue?",
+                    style("CHECK").dim(),
+                    targets.len(),
+                ))
+                .interact()?
+        {
+            return Ok(());
+        }
+
+        let mut deleted = 0;
+        for trashed in targets {
+            let path = trashed.path.to_string();
+            match trashed.empty() {
+                Ok(_) => deleted += 1,
+                Err(e) => warn!("Could not delete {}: {}", path, e),
+            }
+        }
+
+        info!("Deleted {} repositories permanently.", deleted);
+
+        Ok(())
+    }
+}
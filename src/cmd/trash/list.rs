@@ -0,0 +1,34 @@
+use anyhow::Result;
+use chrono::Local;
+use clap::Parser;
+use console::style;
+
+use crate::config::Config;
+use crate::root::Roots;
+use crate::trash::Trashed;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        for trashed in Trashed::list(&roots)? {
+            println!(
+                "{} {}",
+                style(
+                    trashed
+                        .deleted_at
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M:%S"),
+                )
+                .dim(),
+                trashed.path,
+            );
+        }
+
+        Ok(())
+    }
+}
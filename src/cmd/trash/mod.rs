@@ -0,0 +1,33 @@
+mod empty;
+mod list;
+mod restore;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Subcommand)]
+pub enum Action {
+    /// Lists the deleted repositories in the trash.
+    List(list::Cmd),
+    /// Restores a deleted repository to its original location.
+    Restore(restore::Cmd),
+    /// Deletes the repositories in the trash permanently.
+    Empty(empty::Cmd),
+}
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    #[clap(subcommand)]
+    action: Action,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        use Action::*;
+        match self.action {
+            List(cmd) => cmd.run(),
+            Restore(cmd) => cmd.run(),
+            Empty(cmd) => cmd.run(),
+        }
+    }
+}
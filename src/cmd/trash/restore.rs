@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use tracing::info;
+
+use crate::config::Config;
+use crate::root::Roots;
+use crate::trash::Trashed;
+use crate::url::Url;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// URL or pattern of the repository to restore.
+    /// The latest deleted one is restored if it was deleted several times.
+    repo: String,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let url = Url::from_str(
+            &self.repo,
+            &config.patterns,
+            config.defaults.owner.as_deref(),
+        )?;
+
+        let trashed = Trashed::list(&roots)?
+            .into_iter()
+            .rfind(|t| {
+                t.path.host == url.host.to_string()
+                    && t.path.owner == url.owner
+                    && t.path.repo == url.repo
+            })
+            .ok_or_else(|| anyhow!("Could not find the repository in the trash: {}", url))?;
+
+        let path = trashed.restore()?;
+
+        info!(
+            "Restored the repository successfully to: {}",
+            PathBuf::from(&path).to_string_lossy(),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use tracing::info;
+
+use crate::archive::Archived;
+use crate::config::Config;
+use crate::console::Spinner;
+use crate::root::Roots;
+use crate::url::Url;
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// URLs or patterns of the repositories to restore from their archives.
+    #[clap(required = true)]
+    repo: Vec<String>,
+}
+
+impl Cmd {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+        let mut archives = Archived::list(&roots)?;
+
+        for repo in &self.repo {
+            let url = Url::from_str(repo, &config.patterns, config.defaults.owner.as_deref())?;
+            let position = archives
+                .iter()
+                .position(|a| {
+                    a.path.host == url.host.to_string()
+                        && a.path.owner == url.owner
+                        && a.path.repo == url.repo
+                })
+                .ok_or_else(|| anyhow!("Could not find the archive of the repository: {}", url))?;
+
+            let archived = archives.swap_remove(position);
+            let path = Spinner::new("Unpacking the repository...")
+                .spin_while(|| async { archived.extract() })
+                .await?;
+
+            info!(
+                "Restored the repository successfully to: {}",
+                PathBuf::from(&path).to_string_lossy(),
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use git2::Repository;
+use itertools::Itertools;
+use tracing::{info, warn};
+
+use crate::cmd::relocate::relocate;
+use crate::config::Config;
+use crate::index::Index;
+use crate::path::Path;
+use crate::repository::Repositories;
+use crate::root::Roots;
+use crate::url::Url;
+
+enum Problem {
+    /// The directory listed in the index does not exist.
+    Missing,
+    /// The directory is not a Git repository.
+    NotRepository,
+    /// The repository has no remotes that can be parsed as a URL.
+    NoRemotes,
+    /// None of the remotes point to the location of the repository.
+    Mismatch(Url),
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "the directory does not exist"),
+            Self::NotRepository => write!(f, "not a Git repository"),
+            Self::NoRemotes => write!(f, "no remotes with a supported URL"),
+            Self::Mismatch(url) => write!(
+                f,
+                "the remote points to {}:{}/{}",
+                url.host, url.owner, url.repo,
+            ),
+        }
+    }
+}
+
+fn check(config: &Config, roots: &Roots, path: &Path) -> Result<Option<Problem>> {
+    let dir = PathBuf::from(path);
+    if !dir.exists() {
+        return Ok(Some(Problem::Missing));
+    }
+
+    let Ok(repo) = Repository::open(&dir) else {
+        return Ok(Some(Problem::NotRepository));
+    };
+
+    // Prefers origin to decide where to move the repository.
+    let urls = repo
+        .remotes()?
+        .iter()
+        .flatten()
+        .sorted_by_key(|name| *name != "origin")
+        .filter_map(|name| repo.find_remote(name).ok()?.url().map(|u| u.to_string()))
+        .filter_map(|u| Url::from_str(&u, &config.patterns, config.defaults.owner.as_deref()).ok())
+        .collect::<Vec<_>>();
+
+    let Some(first) = urls.first() else {
+        return Ok(Some(Problem::NoRemotes));
+    };
+
+    // Forks often have both origin and upstream, so any of the remotes may match.
+    for url in &urls {
+        let expected = Path::resolve(roots.resolve(url, &config.rules)?, url);
+        if PathBuf::from(&expected) == dir {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(Problem::Mismatch(first.clone())))
+}
+
+#[derive(Debug, Parser)]
+pub struct Cmd {
+    /// Moves repositories to the location of their remote, and drops missing ones from the index.
+    /// Other problems are only reported, as they need to be resolved by hand.
+    #[clap(long)]
+    fix: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let config = Config::load()?;
+        let roots = Roots::find(&config)?;
+
+        let mut remaining = 0;
+        for path in Repositories::try_collect(&roots)?
+            .into_iter()
+            .map(|(p, _)| p)
+            .sorted_by_key(|p| p.to_string())
+        {
+            let Some(problem) = check(&config, &roots, &path)? else {
+                continue;
+            };
+
+            warn!("{}: {}", path, problem);
+
+            let fixed = match (&problem, self.fix) {
+                (Problem::Missing, true) => Index::remove(&path).map(|_| true),
+                (Problem::Mismatch(url), true) => {
+                    relocate(&config, &roots, &path, url, false).map(|_| true)
+                }
+                _ => Ok(false),
+            };
+
+            match fixed {
+                Ok(true) => info!("Fixed: {}", path),
+                Ok(false) => remaining += 1,
+                Err(e) => {
+                    warn!("Could not fix {}: {}", path, e);
+                    remaining += 1;
+                }
+            }
+        }
+
+        if remaining > 0 {
+            bail!("Found {} problems to be resolved.", remaining);
+        }
+
+        info!("All repositories are placed correctly.");
+
+        Ok(())
+    }
+}
@@ -1,20 +1,36 @@
+use std::env::var;
 use std::fs::read_to_string;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
+use dirs::home_dir;
 use serde::Deserialize;
+use toml::{Table, Value};
+use tracing::warn;
 
 use crate::application::Applications;
 use crate::git::Config as GitConfig;
+use crate::layout::Layout;
+use crate::path::expand_home;
 use crate::platform::Config as PlatformConfig;
 use crate::profile::Profiles;
-use crate::root::Root;
+use crate::root::{Config as RootConfig, Root};
 use crate::rule::Rules;
+use crate::subscription::{CONFIG_FILE_NAME, Subscription, Subscriptions};
+use crate::template::Templates;
 use crate::url::Patterns;
 
+const XDG_CONFIG_HOME_KEY: &str = "XDG_CONFIG_HOME";
+const XDG_DIR_NAME: &str = "ghr";
+const XDG_CONFIG_FILE_NAME: &str = "config.toml";
+const INCLUDE_KEY: &str = "include";
+const SUBSCRIBABLE_KEYS: &[&str] = &["patterns", "profiles", "rules", "applications", "templates"];
+
 #[derive(Debug, Default, Deserialize)]
 pub struct Defaults {
     pub owner: Option<String>,
+    /// Name of the initial branch of repositories created by `ghr init`.
+    pub branch: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -22,6 +38,10 @@ pub struct Config {
     #[serde(default)]
     pub defaults: Defaults,
     #[serde(default)]
+    pub layout: Layout,
+    #[serde(default)]
+    pub roots: RootConfig,
+    #[serde(default)]
     pub git: GitConfig,
     #[serde(default)]
     pub platforms: PlatformConfig,
@@ -33,43 +53,255 @@ pub struct Config {
     pub applications: Applications,
     #[serde(default)]
     pub rules: Rules,
+    #[serde(default)]
+    pub templates: Templates,
 }
 
 impl Config {
     pub fn load_from(root: &Root) -> Result<Self> {
-        Ok(Self::load_from_path(root.path().join("ghr.toml"))?.unwrap_or_default())
+        let own = Self::load_own_table(root)?;
+        let mut table = Table::new();
+        for subscription in Subscriptions::list(root)? {
+            match Self::try_subscription(&table, &own, &subscription) {
+                Ok(t) => table = t,
+                Err(e) => warn!(
+                    "Skipped the broken subscription {}: {:#}",
+                    subscription.name, e,
+                ),
+            }
+        }
+
+        merge(&mut table, own, true);
+        Self::load_from_table(table)
+    }
+
+    /// Loads the configuration without merging any subscriptions, such as to unsubscribe a broken one.
+    pub fn load_from_without_subscriptions(root: &Root) -> Result<Self> {
+        Self::load_from_table(Self::load_own_table(root)?)
+    }
+
+    /// Checks that the subscription can be merged into the configuration with the other ones.
+    pub fn check_subscription(root: &Root, subscription: &Subscription) -> Result<()> {
+        let own = Self::load_own_table(root)?;
+        let mut table = Table::new();
+        for other in Subscriptions::list(root)? {
+            if other.path == subscription.path {
+                continue;
+            }
+            if let Ok(t) = Self::try_subscription(&table, &own, &other) {
+                table = t;
+            }
+        }
+
+        Self::try_subscription(&table, &own, subscription).map(|_| ())
+    }
+
+    /// Merges the subscription into the subscribed tables, if the configuration resolves with it.
+    fn try_subscription(table: &Table, own: &Table, subscription: &Subscription) -> Result<Table> {
+        let mut table = table.clone();
+        if let Some(t) = Self::load_table_from_path(subscription.config_path(), &mut Vec::new())? {
+            merge(&mut table, Self::subscribed(t, &subscription.name), true);
+        }
+
+        let mut merged = table.clone();
+        merge(&mut merged, own.clone(), true);
+        Self::load_from_table(merged)?;
+
+        Ok(table)
+    }
+
+    /// Loads the files of your own, excluding the subscriptions.
+    fn load_own_table(root: &Root) -> Result<Table> {
+        let mut table = Table::new();
+        for path in [Self::xdg_path(), Some(root.path().join(CONFIG_FILE_NAME))]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(t) = Self::load_table_from_path(&path, &mut Vec::new())? {
+                merge(&mut table, t, true);
+            }
+        }
+
+        Ok(table)
     }
 
     pub fn load() -> Result<Self> {
         Self::load_from(&Root::find()?)
     }
 
-    fn load_from_path<P>(path: P) -> Result<Option<Self>>
+    /// Keeps only the keys that are allowed to be shared through subscriptions.
+    fn subscribed(mut table: Table, name: &str) -> Table {
+        table.retain(|key, _| match SUBSCRIBABLE_KEYS.contains(&key) {
+            true => true,
+            _ => {
+                warn!(
+                    "Ignored '{}' in the subscribed configuration: {}",
+                    key, name
+                );
+                false
+            }
+        });
+
+        table
+    }
+
+    fn xdg_path() -> Option<PathBuf> {
+        var(XDG_CONFIG_HOME_KEY)
+            .ok()
+            .filter(|s| !s.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|h| h.join(".config")))
+            .map(|p| p.join(XDG_DIR_NAME).join(XDG_CONFIG_FILE_NAME))
+    }
+
+    /// Loads a TOML table from the path, merging the included files beneath it.
+    fn load_table_from_path<P>(path: P, stack: &mut Vec<PathBuf>) -> Result<Option<Table>>
     where
         P: AsRef<Path>,
     {
-        Ok(match path.as_ref().exists() {
-            true => Some(Self::load_from_str(read_to_string(path)?.as_str())?),
-            _ => None,
-        })
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let canonical = path.canonicalize()?;
+        if stack.contains(&canonical) {
+            bail!(
+                "Circular include detected in the configuration: {}",
+                canonical.to_string_lossy(),
+            );
+        }
+
+        let mut own = toml::from_str::<Table>(&read_to_string(path)?)
+            .with_context(|| format!("Could not load the configuration: {}", path.display()))?;
+
+        let includes = match own.remove(INCLUDE_KEY) {
+            Some(v) => Vec::<String>::deserialize(v)
+                .with_context(|| format!("'{}' must be an array of paths", INCLUDE_KEY))?,
+            _ => Vec::new(),
+        };
+
+        stack.push(canonical);
+
+        let mut table = Table::new();
+        for include in includes {
+            let included = Self::resolve_include(path, &include)?;
+            match Self::load_table_from_path(&included, stack)? {
+                Some(t) => merge(&mut table, t, true),
+                _ => bail!(
+                    "Could not find the included configuration: {}",
+                    included.to_string_lossy(),
+                ),
+            }
+        }
+
+        stack.pop();
+        merge(&mut table, own, true);
+
+        Ok(Some(table))
+    }
+
+    fn resolve_include(from: &Path, include: &str) -> Result<PathBuf> {
+        // Joining an absolute path, such as the expanded home, replaces the parent.
+        let include = expand_home(include)?;
+        Ok(from.parent().map(|d| d.join(&include)).unwrap_or(include))
     }
 
+    fn load_from_table(table: Table) -> Result<Self> {
+        Self::deserialize(table)?.with_defaults()
+    }
+
+    #[cfg(test)]
     fn load_from_str(s: &str) -> Result<Self> {
-        Ok(toml::from_str::<Self>(s)?.with_defaults())
+        toml::from_str::<Self>(s)?.with_defaults()
     }
 
-    fn with_defaults(mut self) -> Self {
+    fn with_defaults(mut self) -> Result<Self> {
         self.patterns = self.patterns.with_defaults();
-        self
+        self.profiles.resolve_extends()?;
+        Ok(self)
+    }
+}
+
+/// Merges a configuration layer into the lower one.
+/// Top-level arrays such as patterns and rules are concatenated, putting the higher layer first.
+/// Tables are merged recursively, and any other values are overridden by the higher layer.
+fn merge(lower: &mut Table, higher: Table, top_level: bool) {
+    for (key, value) in higher {
+        match (lower.get_mut(&key), value) {
+            (Some(Value::Table(l)), Value::Table(h)) => merge(l, h, false),
+            (Some(Value::Array(l)), Value::Array(mut h)) if top_level => {
+                h.append(l);
+                *l = h;
+            }
+            (_, v) => {
+                lower.insert(key, v);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::config::Config;
+    use super::*;
 
     #[test]
     fn load_example_config() {
         Config::load_from_str(include_str!("../ghr.example.toml")).unwrap();
     }
+
+    #[test]
+    fn merge_layers() {
+        let mut lower = toml::from_str::<Table>(
+            r#"
+            [defaults]
+            owner = "lower"
+
+            [profiles.work]
+            user.name = "Lower Name"
+            user.email = "lower@example.com"
+
+            [[rules]]
+            profile.name = "lower"
+            "#,
+        )
+        .unwrap();
+
+        let higher = toml::from_str::<Table>(
+            r#"
+            [defaults]
+            owner = "higher"
+
+            [profiles.work]
+            user.email = "higher@example.com"
+
+            [[rules]]
+            profile.name = "higher"
+            "#,
+        )
+        .unwrap();
+
+        merge(&mut lower, higher, true);
+
+        assert_eq!(
+            toml::from_str::<Table>(
+                r#"
+                [defaults]
+                owner = "higher"
+
+                [profiles.work]
+                user.name = "Lower Name"
+                user.email = "higher@example.com"
+
+                [[rules]]
+                profile.name = "higher"
+
+                [[rules]]
+                profile.name = "lower"
+                "#,
+            )
+            .unwrap(),
+            lower,
+        );
+    }
 }
@@ -5,8 +5,35 @@ use std::time::Duration;
 
 use console::style;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use itertools::Itertools;
 use tokio::task::{JoinError, JoinHandle};
 
+/// Placeholder of a column without any value.
+pub const NONE: &str = "-";
+
+/// Prints the rows aligning their columns.
+pub fn print_rows(rows: Vec<Vec<String>>) {
+    let widths = rows.iter().fold(Vec::<usize>::new(), |mut widths, row| {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (w, column) in widths.iter_mut().zip(row) {
+            *w = (*w).max(column.chars().count());
+        }
+
+        widths
+    });
+
+    for row in rows {
+        println!(
+            "{}",
+            row.iter()
+                .zip(&widths)
+                .map(|(column, width)| format!("{:<width$}", column, width = width))
+                .join("  ")
+                .trim_end(),
+        );
+    }
+}
+
 fn create_spinner(message: impl Into<Cow<'static, str>>) -> ProgressBar {
     let spinner = ProgressStyle::with_template("{prefix} {spinner} {wide_msg}")
         .unwrap()
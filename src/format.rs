@@ -0,0 +1,250 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{Error, Result, bail};
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::manifest::Kind;
+use crate::path::{PartialPath, Path};
+
+/// Format to print structured output of commands in.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Tsv,
+    /// Renders each record into a template such as `{{owner}}/{{repo}}`.
+    Template(String),
+    /// Manifest of other multi-repository tools, only supported by `sync` commands.
+    Manifest(Kind),
+}
+
+impl Format {
+    /// Prints the records, or calls the fallback if the format is `Text`.
+    pub fn print<R, F>(&self, records: &[R], text: F) -> Result<()>
+    where
+        R: Record,
+        F: Fn(&R) -> String,
+    {
+        match self {
+            Self::Text => records.iter().for_each(|r| println!("{}", text(r))),
+            Self::Json => println!("{}", serde_json::to_string_pretty(records)?),
+            Self::Tsv => records
+                .iter()
+                .flat_map(|r| r.rows())
+                .for_each(|row| println!("{}", row.iter().map(|c| escape_tsv(c)).join("\t"))),
+            Self::Template(t) => records
+                .iter()
+                .for_each(|r| println!("{}", render(t, &r.fields()))),
+            Self::Manifest(k) => bail!("The format {} is not supported by this command", k),
+        }
+
+        Ok(())
+    }
+
+    /// Prints a single record, or calls the fallback if the format is `Text`.
+    pub fn print_one<R, F>(&self, record: &R, text: F) -> Result<()>
+    where
+        R: Record,
+        F: Fn(&R) -> String,
+    {
+        match self {
+            Self::Json => println!("{}", serde_json::to_string_pretty(record)?),
+            _ => self.print(std::slice::from_ref(record), text)?,
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "text" => Self::Text,
+            "json" => Self::Json,
+            "tsv" => Self::Tsv,
+            _ if s.contains("{{") => Self::Template(s.to_string()),
+            _ => match Kind::from_str(s) {
+                Ok(k) => Self::Manifest(k),
+                _ => bail!(
+                    "Unknown format: {} (expected text, json, tsv, vcstool, repo, mr or a template like '{{{{repo}}}}')",
+                    s
+                ),
+            },
+        })
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+            Self::Tsv => write!(f, "tsv"),
+            Self::Template(t) => write!(f, "{}", t),
+            Self::Manifest(k) => write!(f, "{}", k),
+        }
+    }
+}
+
+/// An item of structured output.
+pub trait Record: Serialize {
+    /// Named values to be substituted into templates.
+    fn fields(&self) -> Vec<(String, String)>;
+
+    /// Rows to print in TSV. Defaults to a row of all the field values.
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![self.fields().into_iter().map(|(_, v)| v).collect()]
+    }
+}
+
+/// A repository, or a directory of a host or an owner.
+#[derive(Debug, Serialize)]
+pub struct PathRecord {
+    pub root: String,
+    pub host: Option<String>,
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<u32>,
+}
+
+impl PathRecord {
+    pub fn with_score(mut self, score: u32) -> Self {
+        self.score = Some(score);
+        self
+    }
+}
+
+impl From<&Path<'_>> for PathRecord {
+    fn from(p: &Path<'_>) -> Self {
+        Self {
+            root: p.root().name().to_string(),
+            host: Some(p.host.to_string()),
+            owner: Some(p.owner.to_string()),
+            repo: Some(p.repo.to_string()),
+            path: PathBuf::from(p).to_string_lossy().to_string(),
+            score: None,
+        }
+    }
+}
+
+impl From<&PartialPath<'_>> for PathRecord {
+    fn from(p: &PartialPath<'_>) -> Self {
+        Self {
+            root: p.root.name().to_string(),
+            host: p.host.clone(),
+            owner: p.owner.clone(),
+            repo: p.repo.clone(),
+            path: PathBuf::from(p).to_string_lossy().to_string(),
+            score: None,
+        }
+    }
+}
+
+impl Record for PathRecord {
+    fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = vec![
+            ("root".to_string(), self.root.to_string()),
+            ("host".to_string(), self.host.clone().unwrap_or_default()),
+            ("owner".to_string(), self.owner.clone().unwrap_or_default()),
+            ("repo".to_string(), self.repo.clone().unwrap_or_default()),
+            ("path".to_string(), self.path.to_string()),
+        ];
+
+        if let Some(score) = self.score {
+            fields.push(("score".to_string(), score.to_string()));
+        }
+
+        fields
+    }
+}
+
+fn escape_tsv(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+/// Substitutes `{{name}}` in the template. Unknown names are substituted with an empty string.
+pub fn render(template: &str, fields: &[(String, String)]) -> String {
+    substitute(template, fields, false)
+}
+
+/// Substitutes `{{name}}` in the template, leaving unknown ones as they are.
+/// Used for files which may contain placeholders of other tools, such as `${{ github.ref }}`.
+pub fn render_known(template: &str, fields: &[(String, String)]) -> String {
+    substitute(template, fields, true)
+}
+
+fn substitute(template: &str, fields: &[(String, String)], keep_unknown: bool) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+
+        let name = rest[start + 2..start + end].trim();
+        output.push_str(&rest[..start]);
+        match fields.iter().find(|(k, _)| k == name) {
+            Some((_, v)) => output.push_str(v),
+            _ if keep_unknown => output.push_str(&rest[start..start + end + 2]),
+            _ => {}
+        }
+
+        rest = &rest[start + end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_format() {
+        assert_eq!(Format::Json, Format::from_str("json").unwrap());
+        assert_eq!(Format::Tsv, Format::from_str("TSV").unwrap());
+        assert_eq!(
+            Format::Template("{{owner}}/{{repo}}".to_string()),
+            Format::from_str("{{owner}}/{{repo}}").unwrap(),
+        );
+        assert_eq!(
+            Format::Manifest(Kind::Vcstool),
+            Format::from_str("vcstool").unwrap(),
+        );
+        assert!(Format::from_str("yaml").is_err());
+    }
+
+    #[test]
+    fn render_template() {
+        let fields = vec![
+            ("owner".to_string(), "siketyan".to_string()),
+            ("repo".to_string(), "ghr".to_string()),
+        ];
+
+        assert_eq!("siketyan/ghr", render("{{owner}}/{{repo}}", &fields));
+        assert_eq!("ghr@", render("{{ repo }}@{{unknown}}", &fields));
+        assert_eq!("ghr {{broken", render("{{repo}} {{broken", &fields));
+    }
+
+    #[test]
+    fn render_known_fields() {
+        let fields = vec![("repo".to_string(), "ghr".to_string())];
+
+        assert_eq!(
+            "name: ghr\nref: ${{ github.ref }}",
+            render_known("name: {{ repo }}\nref: ${{ github.ref }}", &fields),
+        );
+        assert_eq!("{{a}}{{b}}", render_known("{{a}}{{b}}", &fields));
+    }
+}
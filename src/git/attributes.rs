@@ -0,0 +1,70 @@
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+/// Lines of `info/attributes` in a Git directory, which is not shared like `.gitattributes`.
+#[derive(Debug, Default)]
+pub struct File {
+    lines: Vec<String>,
+}
+
+impl File {
+    /// Loads the attributes of the Git directory, or nothing if the file does not exist.
+    pub fn load<P>(git_dir: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = Self::file_path(git_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(Self {
+            lines: read_to_string(path)?
+                .lines()
+                .map(|l| l.to_string())
+                .collect(),
+        })
+    }
+
+    pub fn save<P>(&self, git_dir: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = Self::file_path(git_dir);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+
+        let mut content = self.lines.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+
+        write(path, content)?;
+
+        Ok(())
+    }
+
+    pub fn contains(&self, line: &str) -> bool {
+        self.lines.iter().any(|l| l == line)
+    }
+
+    pub fn remove(&mut self, line: &str) {
+        self.lines.retain(|l| l != line);
+    }
+
+    pub fn add_or_noop(&mut self, line: &str) {
+        if !self.contains(line) {
+            self.lines.push(line.to_string());
+        }
+    }
+
+    fn file_path<P>(git_dir: P) -> PathBuf
+    where
+        P: AsRef<Path>,
+    {
+        git_dir.as_ref().join("info").join("attributes")
+    }
+}
@@ -10,6 +10,8 @@ pub struct StrategyConfig {
     pub fetch: Strategy,
     #[serde(default)]
     pub checkout: Strategy,
+    #[serde(default)]
+    pub pull: Strategy,
 }
 
 #[derive(Debug, Default, Deserialize)]
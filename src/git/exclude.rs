@@ -91,8 +91,16 @@ impl File {
         self.write(StdFile::create(Self::file_path(path))?)
     }
 
+    pub fn contains(&self, node: &Node) -> bool {
+        self.nodes.contains(node)
+    }
+
+    pub fn remove(&mut self, node: &Node) {
+        self.nodes.retain(|n| n != node);
+    }
+
     pub fn add_or_noop(&mut self, node: Node) {
-        if !self.nodes.contains(&node) {
+        if !self.contains(&node) {
             self.nodes.push(node);
         }
     }
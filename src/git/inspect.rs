@@ -0,0 +1,97 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::Path;
+
+use anyhow::Result;
+use git2::{BranchType, ErrorCode, Repository, Status, StatusOptions};
+
+/// Local state of a repository that would be lost by deleting its working directory.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Finding {
+    Uncommitted(usize),
+    Untracked(usize),
+    Stashes(usize),
+    NoUpstream(String),
+    Ahead { branch: String, commits: usize },
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Uncommitted(n) => write!(f, "{} uncommitted changes", n),
+            Self::Untracked(n) => write!(f, "{} untracked files", n),
+            Self::Stashes(n) => write!(f, "{} stashes", n),
+            Self::NoUpstream(b) => write!(f, "branch '{}' has no upstream", b),
+            Self::Ahead { branch, commits } => write!(
+                f,
+                "branch '{}' is {} commits ahead of its upstream",
+                branch, commits,
+            ),
+        }
+    }
+}
+
+/// Inspects the repository for work that has not been pushed anywhere.
+pub fn inspect<P>(path: P) -> Result<Vec<Finding>>
+where
+    P: AsRef<Path>,
+{
+    let mut repo = Repository::open(path)?;
+    let mut findings = Vec::new();
+
+    let (mut uncommitted, mut untracked) = (0, 0);
+    for entry in repo
+        .statuses(Some(
+            StatusOptions::new()
+                .include_untracked(true)
+                .include_ignored(false)
+                .recurse_untracked_dirs(true),
+        ))?
+        .iter()
+    {
+        match entry.status() {
+            Status::WT_NEW => untracked += 1,
+            _ => uncommitted += 1,
+        }
+    }
+
+    if uncommitted > 0 {
+        findings.push(Finding::Uncommitted(uncommitted));
+    }
+    if untracked > 0 {
+        findings.push(Finding::Untracked(untracked));
+    }
+
+    let mut stashes = 0;
+    repo.stash_foreach(|_, _, _| {
+        stashes += 1;
+        true
+    })?;
+    if stashes > 0 {
+        findings.push(Finding::Stashes(stashes));
+    }
+
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        let name = branch.name()?.unwrap_or_default().to_string();
+        let upstream = match branch.upstream() {
+            Ok(u) => u,
+            Err(e) if e.code() == ErrorCode::NotFound => {
+                findings.push(Finding::NoUpstream(name));
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if let (Some(local), Some(remote)) = (branch.get().target(), upstream.get().target()) {
+            let (ahead, _) = repo.graph_ahead_behind(local, remote)?;
+            if ahead > 0 {
+                findings.push(Finding::Ahead {
+                    branch: name,
+                    commits: ahead,
+                });
+            }
+        }
+    }
+
+    Ok(findings)
+}
@@ -1,7 +1,9 @@
 mod config;
 mod strategy;
 
+pub mod attributes;
 pub mod exclude;
+pub mod inspect;
 
 pub use config::Config;
 
@@ -40,3 +42,9 @@ pub trait CheckoutBranch {
     where
         P: AsRef<Path>;
 }
+
+pub trait Pull {
+    fn pull<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>;
+}
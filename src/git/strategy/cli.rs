@@ -4,7 +4,7 @@ use std::process::Command;
 use anyhow::anyhow;
 use tracing::debug;
 
-use crate::git::{CheckoutBranch, CloneOptions, CloneRepository, Fetch};
+use crate::git::{CheckoutBranch, CloneOptions, CloneRepository, Fetch, Pull};
 
 pub struct Cli;
 
@@ -95,3 +95,23 @@ impl CheckoutBranch for Cli {
         }
     }
 }
+
+impl Pull for Cli {
+    fn pull<P>(&self, path: P) -> anyhow::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let output = Command::new("git")
+            .current_dir(path)
+            .args(["pull", "--ff-only"])
+            .output()?;
+
+        match output.status.success() {
+            true => Ok(()),
+            _ => Err(anyhow!(
+                "Error occurred while pulling the remote: {}",
+                String::from_utf8_lossy(output.stderr.as_slice()).trim(),
+            )),
+        }
+    }
+}
@@ -6,7 +6,7 @@ use std::path::Path;
 
 use serde::Deserialize;
 
-use crate::git::{CheckoutBranch, CloneOptions, CloneRepository, Fetch};
+use crate::git::{CheckoutBranch, CloneOptions, CloneRepository, Fetch, Pull};
 
 #[derive(Debug, Default, Deserialize)]
 pub enum Strategy {
@@ -52,3 +52,14 @@ impl CheckoutBranch for Strategy {
         }
     }
 }
+
+impl Pull for Strategy {
+    fn pull<P>(&self, path: P) -> anyhow::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        match self {
+            Self::Cli => Cli.pull(path),
+        }
+    }
+}
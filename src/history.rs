@@ -0,0 +1,191 @@
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::path::Path;
+use crate::root::{Root, Roots};
+use crate::store::{LOCK, Version, save};
+
+const HISTORY_FILE_NAME: &str = "history.toml";
+
+// Keeps the history small enough to be read on every search.
+const MAX_ENTRIES: usize = 500;
+
+/// Visits of a repository, used to rank it by frecency.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Entry {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    pub count: u32,
+    pub last: DateTime<Utc>,
+}
+
+impl Entry {
+    fn is(&self, p: &Path) -> bool {
+        self.host == p.host && self.owner == p.owner && self.repo == p.repo
+    }
+
+    /// Number of visits weighted by how recently the repository was visited.
+    pub fn frecency(&self, now: DateTime<Utc>) -> f64 {
+        let elapsed = now.signed_duration_since(self.last);
+        let weight = if elapsed < TimeDelta::hours(1) {
+            4.0
+        } else if elapsed < TimeDelta::days(1) {
+            2.0
+        } else if elapsed < TimeDelta::weeks(1) {
+            1.0
+        } else if elapsed < TimeDelta::days(30) {
+            0.5
+        } else {
+            0.25
+        };
+
+        f64::from(self.count) * weight
+    }
+}
+
+/// Repositories visited through `cd`, `open` and `path`, stored under the root.
+#[derive(Deserialize, Serialize)]
+pub struct History {
+    version: Version,
+
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            version: Version::V1,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl History {
+    /// Loads the history of the root. Returns an empty history if there is no usable one.
+    pub fn load(root: &Root) -> Result<Self> {
+        let path = Self::file_path(root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(match toml::from_str(&read_to_string(&path)?) {
+            Ok(h) => h,
+            Err(e) => {
+                warn!(
+                    "Ignored the broken history {}: {}",
+                    path.to_string_lossy(),
+                    e
+                );
+                Self::default()
+            }
+        })
+    }
+
+    /// Records a visit to the repository in the history of its root.
+    pub fn record(path: &Path) -> Result<()> {
+        let _lock = LOCK.lock().unwrap();
+
+        let root = path.root();
+        let now = Utc::now();
+        let mut history = Self::load(root)?;
+
+        match history.entries.iter_mut().find(|e| e.is(path)) {
+            Some(e) => {
+                e.count = e.count.saturating_add(1);
+                e.last = now;
+            }
+            _ => history.entries.push(Entry {
+                host: path.host.to_string(),
+                owner: path.owner.to_string(),
+                repo: path.repo.to_string(),
+                count: 1,
+                last: now,
+            }),
+        }
+
+        if history.entries.len() > MAX_ENTRIES {
+            history.entries = history
+                .entries
+                .into_iter()
+                .sorted_by(|a, b| b.frecency(now).total_cmp(&a.frecency(now)))
+                .take(MAX_ENTRIES)
+                .collect();
+        }
+
+        history.save(root)
+    }
+
+    /// Records a visit, only warning on failure not to interrupt the command.
+    pub fn record_or_warn(path: &Path) {
+        if let Err(e) = Self::record(path) {
+            warn!("Could not record the visit to {}: {}", path, e);
+        }
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.is(path))
+    }
+
+    fn save(&self, root: &Root) -> Result<()> {
+        save(&Self::file_path(root), self)
+    }
+
+    fn file_path(root: &Root) -> PathBuf {
+        root.data_path().join(HISTORY_FILE_NAME)
+    }
+}
+
+/// Histories of all the roots.
+pub struct Histories<'a> {
+    histories: Vec<(&'a Root, History)>,
+}
+
+impl<'a> Histories<'a> {
+    pub fn load(roots: &'a Roots) -> Result<Self> {
+        Ok(Self {
+            histories: roots
+                .iter()
+                .map(|root| Ok((root, History::load(root)?)))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&Entry> {
+        self.histories
+            .iter()
+            .find(|(root, _)| *root == path.root())
+            .and_then(|(_, history)| history.get(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weigh_recent_visits() {
+        let now = Utc::now();
+        let entry = |count, elapsed| Entry {
+            host: "github.com".to_string(),
+            owner: "siketyan".to_string(),
+            repo: "ghr".to_string(),
+            count,
+            last: now - elapsed,
+        };
+
+        assert_eq!(8.0, entry(2, TimeDelta::minutes(5)).frecency(now));
+        assert_eq!(2.0, entry(2, TimeDelta::days(3)).frecency(now));
+        assert_eq!(0.5, entry(2, TimeDelta::days(60)).frecency(now));
+        assert!(
+            entry(1, TimeDelta::zero()).frecency(now) > entry(3, TimeDelta::days(90)).frecency(now)
+        );
+    }
+}
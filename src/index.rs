@@ -0,0 +1,138 @@
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::path::Path;
+use crate::repository::Repositories;
+use crate::root::{Root, Roots};
+use crate::store::{LOCK, Version, save};
+
+const INDEX_FILE_NAME: &str = "index.toml";
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+pub struct Entry {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl From<&Path<'_>> for Entry {
+    fn from(p: &Path<'_>) -> Self {
+        Self {
+            host: p.host.to_string(),
+            owner: p.owner.to_string(),
+            repo: p.repo.to_string(),
+        }
+    }
+}
+
+/// On-disk list of repositories in a root, to avoid walking the root on every command.
+/// The index is created by `ghr reindex`, and only kept up to date once it exists.
+#[derive(Deserialize, Serialize)]
+pub struct Index {
+    version: Version,
+    layout: String,
+
+    #[serde(default)]
+    repositories: Vec<Entry>,
+}
+
+impl Index {
+    /// Loads the index of the root. Returns `None` if there is no usable index.
+    pub fn load(root: &Root) -> Result<Option<Self>> {
+        let path = Self::file_path(root);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let index = match toml::from_str::<Self>(&read_to_string(&path)?) {
+            Ok(i) => i,
+            Err(e) => {
+                warn!("Ignored the broken index {}: {}", path.to_string_lossy(), e);
+                return Ok(None);
+            }
+        };
+
+        if index.layout != root.layout().to_string() {
+            debug!(
+                "Ignored the index built with another layout: {}",
+                path.to_string_lossy(),
+            );
+            return Ok(None);
+        }
+
+        Ok(Some(index))
+    }
+
+    pub fn exists(root: &Root) -> bool {
+        Self::file_path(root).exists()
+    }
+
+    /// Walks the root and saves the index from scratch.
+    pub fn rebuild(roots: &Roots, root: &Root) -> Result<usize> {
+        let _lock = LOCK.lock().unwrap();
+
+        let index = Self {
+            version: Version::V1,
+            layout: root.layout().to_string(),
+            repositories: Repositories::try_collect_in(root, &roots.others(root))?
+                .into_iter()
+                .map(|(p, _)| Entry::from(&p))
+                .sorted()
+                .collect(),
+        };
+
+        index.save(root)?;
+
+        Ok(index.repositories.len())
+    }
+
+    /// Adds the repository to the index of its root, if the index exists.
+    pub fn insert(path: &Path) -> Result<()> {
+        Self::update(path.root(), |entries| {
+            let entry = Entry::from(path);
+            if !entries.contains(&entry) {
+                entries.push(entry);
+                entries.sort();
+            }
+        })
+    }
+
+    /// Removes the repository from the index of its root, if the index exists.
+    pub fn remove(path: &Path) -> Result<()> {
+        Self::update(path.root(), |entries| {
+            let entry = Entry::from(path);
+            entries.retain(|e| e != &entry);
+        })
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.repositories
+    }
+
+    fn update<F>(root: &Root, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Vec<Entry>),
+    {
+        let _lock = LOCK.lock().unwrap();
+
+        if let Some(mut index) = Self::load(root)? {
+            f(&mut index.repositories);
+            index.save(root)?;
+        }
+
+        Ok(())
+    }
+
+    fn save(&self, root: &Root) -> Result<()> {
+        save(&Self::file_path(root), self)
+    }
+
+    fn file_path(root: &Root) -> PathBuf {
+        root.data_path().join(INDEX_FILE_NAME)
+    }
+}
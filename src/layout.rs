@@ -0,0 +1,430 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Error, Result, anyhow, bail};
+use serde_with::DeserializeFromStr;
+
+use crate::url::Host;
+
+pub const DEFAULT_LAYOUT: &str = "{host}/{owner}/{repo}";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Placeholder {
+    Host,
+    Owner,
+    Repo,
+}
+
+impl FromStr for Placeholder {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "host" => Self::Host,
+            "owner" => Self::Owner,
+            "repo" => Self::Repo,
+            _ => Err(anyhow!(
+                "Unknown placeholder found in the layout: {{{}}}",
+                s
+            ))?,
+        })
+    }
+}
+
+impl Display for Placeholder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Host => write!(f, "{{host}}"),
+            Self::Owner => write!(f, "{{owner}}"),
+            Self::Repo => write!(f, "{{repo}}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// Values of the placeholders extracted from a path, or to be rendered into a path.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Values {
+    pub host: Option<String>,
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+}
+
+impl Values {
+    fn get(&self, p: Placeholder) -> Option<&str> {
+        match p {
+            Placeholder::Host => self.host.as_deref(),
+            Placeholder::Owner => self.owner.as_deref(),
+            Placeholder::Repo => self.repo.as_deref(),
+        }
+    }
+
+    fn set(&mut self, p: Placeholder, value: String) {
+        match p {
+            Placeholder::Host => self.host = Some(value),
+            Placeholder::Owner => self.owner = Some(value),
+            Placeholder::Repo => self.repo = Some(value),
+        }
+    }
+}
+
+/// Template of the directory structure under the root, such as `{host}/{owner}/{repo}`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, DeserializeFromStr)]
+pub struct Layout {
+    template: String,
+    segments: Vec<Vec<Part>>,
+}
+
+impl Layout {
+    /// Number of directories between the root and a repository.
+    pub fn depth(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn contains(&self, p: Placeholder) -> bool {
+        self.segments
+            .iter()
+            .flatten()
+            .any(|part| part == &Part::Placeholder(p))
+    }
+
+    /// Renders the relative path of a repository.
+    pub fn render(&self, host: &str, owner: &str, repo: &str) -> PathBuf {
+        self.render_partial(&Values {
+            host: Some(host.to_string()),
+            owner: Some(owner.to_string()),
+            repo: Some(repo.to_string()),
+        })
+    }
+
+    /// Renders the leading segments of the layout as long as all of their placeholders are known.
+    pub fn render_partial(&self, values: &Values) -> PathBuf {
+        let mut path = PathBuf::new();
+        for segment in &self.segments {
+            let mut rendered = String::new();
+            for part in segment {
+                match part {
+                    Part::Literal(l) => rendered.push_str(l),
+                    Part::Placeholder(p) => match values.get(*p) {
+                        Some(v) => rendered.push_str(v),
+                        _ => return path,
+                    },
+                }
+            }
+
+            path.push(rendered);
+        }
+
+        path
+    }
+
+    /// Extracts the host, owner and repository name from a path relative to the root.
+    /// Placeholders that are not in the layout are left empty; the host falls back to GitHub.
+    /// Returns `None` if the path does not match, or matches in more than one way.
+    pub fn parse<P>(&self, path: P) -> Option<(String, String, String)>
+    where
+        P: AsRef<Path>,
+    {
+        let mut candidates = self.parse_all(path);
+        match candidates.len() {
+            1 => candidates.pop(),
+            _ => None,
+        }
+    }
+
+    /// Extracts all possible host, owner and repository names from a path relative to the root.
+    /// A literal that can also occur in names makes a path ambiguous, such as `acme-corp-api`
+    /// for `{owner}-{repo}`, so the candidates are returned shortest value first.
+    pub fn parse_all<P>(&self, path: P) -> Vec<(String, String, String)>
+    where
+        P: AsRef<Path>,
+    {
+        let Some(components) = path
+            .as_ref()
+            .iter()
+            .map(|c| c.to_str())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Vec::new();
+        };
+
+        if components.len() != self.segments.len() {
+            return Vec::new();
+        }
+
+        let mut candidates = vec![Values::default()];
+        for (segment, component) in self.segments.iter().zip(components) {
+            candidates = candidates
+                .into_iter()
+                .flat_map(|values| {
+                    let mut matches = Vec::new();
+                    Self::match_segment(segment, component, values, &mut matches);
+                    matches
+                })
+                .collect();
+        }
+
+        candidates
+            .into_iter()
+            .filter_map(|values| {
+                Some((
+                    values.host.unwrap_or_else(|| Host::default().to_string()),
+                    values.owner.unwrap_or_default(),
+                    values.repo?,
+                ))
+            })
+            .collect()
+    }
+
+    fn match_segment(parts: &[Part], s: &str, values: Values, matches: &mut Vec<Values>) {
+        match parts.split_first() {
+            None => {
+                if s.is_empty() {
+                    matches.push(values);
+                }
+            }
+            Some((Part::Literal(l), rest)) => {
+                if let Some(s) = s.strip_prefix(l.as_str()) {
+                    Self::match_segment(rest, s, values, matches);
+                }
+            }
+            Some((Part::Placeholder(p), rest)) => {
+                // Placeholders are always followed by a literal or the end of the segment,
+                // so the value may end at any occurrence of the next literal.
+                let ends = match rest.first() {
+                    Some(Part::Literal(l)) => (1..s.len())
+                        .filter(|i| s.is_char_boundary(*i) && s[*i..].starts_with(l.as_str()))
+                        .collect(),
+                    _ if s.is_empty() => Vec::new(),
+                    _ => vec![s.len()],
+                };
+
+                for end in ends {
+                    let mut values = values.clone();
+                    values.set(*p, s[..end].to_string());
+                    Self::match_segment(rest, &s[end..], values, matches);
+                }
+            }
+        }
+    }
+
+    fn parse_segment(s: &str) -> Result<Vec<Part>> {
+        let mut parts = Vec::new();
+        let mut rest = s;
+        while !rest.is_empty() {
+            match rest.find('{') {
+                Some(0) => {
+                    let end = rest
+                        .find('}')
+                        .ok_or_else(|| anyhow!("Unclosed placeholder in the layout: {}", s))?;
+
+                    if let Some(Part::Placeholder(_)) = parts.last() {
+                        bail!(
+                            "Placeholders must be separated by a literal in the layout: {}",
+                            s
+                        );
+                    }
+
+                    parts.push(Part::Placeholder(Placeholder::from_str(&rest[1..end])?));
+                    rest = &rest[end + 1..];
+                }
+                Some(i) => {
+                    parts.push(Part::Literal(rest[..i].to_string()));
+                    rest = &rest[i..];
+                }
+                _ => {
+                    parts.push(Part::Literal(rest.to_string()));
+                    rest = "";
+                }
+            }
+        }
+
+        Ok(parts)
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self::from_str(DEFAULT_LAYOUT).unwrap()
+    }
+}
+
+impl FromStr for Layout {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let segments = s
+            .trim_matches('/')
+            .split('/')
+            .map(Self::parse_segment)
+            .collect::<Result<Vec<_>>>()?;
+
+        let layout = Self {
+            template: s.to_string(),
+            segments,
+        };
+
+        if layout.segments.iter().any(|s| s.is_empty()) {
+            bail!("The layout must not contain an empty segment: {}", s);
+        }
+
+        for p in [Placeholder::Host, Placeholder::Owner, Placeholder::Repo] {
+            let count = layout
+                .segments
+                .iter()
+                .flatten()
+                .filter(|part| *part == &Part::Placeholder(p))
+                .count();
+
+            if count > 1 {
+                bail!("The layout must contain {} at most once: {}", p, s);
+            }
+        }
+
+        if !layout.contains(Placeholder::Repo) {
+            bail!("The layout must contain {}: {}", Placeholder::Repo, s);
+        }
+
+        Ok(layout)
+    }
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_default() {
+        assert_eq!(
+            PathBuf::from("github.com/siketyan/ghr"),
+            Layout::default().render("github.com", "siketyan", "ghr"),
+        );
+    }
+
+    #[test]
+    fn render_custom() {
+        let layout = Layout::from_str("{host}/{owner}-{repo}").unwrap();
+
+        assert_eq!(
+            PathBuf::from("github.com/siketyan-ghr"),
+            layout.render("github.com", "siketyan", "ghr"),
+        );
+    }
+
+    #[test]
+    fn render_partial() {
+        let layout = Layout::default();
+
+        assert_eq!(
+            PathBuf::from("github.com"),
+            layout.render_partial(&Values {
+                host: Some("github.com".to_string()),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            PathBuf::new(),
+            layout.render_partial(&Values {
+                owner: Some("siketyan".to_string()),
+                ..Default::default()
+            }),
+        );
+    }
+
+    #[test]
+    fn parse_default() {
+        assert_eq!(
+            Some((
+                "github.com".to_string(),
+                "siketyan".to_string(),
+                "ghr".to_string(),
+            )),
+            Layout::default().parse("github.com/siketyan/ghr"),
+        );
+        assert_eq!(None, Layout::default().parse("github.com/siketyan"));
+    }
+
+    #[test]
+    fn parse_custom() {
+        let layout = Layout::from_str("{owner}/{repo}").unwrap();
+        assert_eq!(
+            Some((
+                "github.com".to_string(),
+                "siketyan".to_string(),
+                "ghr".to_string(),
+            )),
+            layout.parse("siketyan/ghr"),
+        );
+
+        let layout = Layout::from_str("{host}/{owner}+{repo}").unwrap();
+        assert_eq!(
+            Some((
+                "example.com".to_string(),
+                "siketyan".to_string(),
+                "my-repo".to_string(),
+            )),
+            layout.parse("example.com/siketyan+my-repo"),
+        );
+    }
+
+    #[test]
+    fn parse_ambiguous() {
+        let layout = Layout::from_str("{host}/{owner}-{repo}").unwrap();
+        let path = layout.render("github.com", "acme-corp", "api");
+
+        assert_eq!(PathBuf::from("github.com/acme-corp-api"), path);
+        assert_eq!(None, layout.parse(&path));
+        assert_eq!(
+            vec![
+                (
+                    "github.com".to_string(),
+                    "acme".to_string(),
+                    "corp-api".to_string(),
+                ),
+                (
+                    "github.com".to_string(),
+                    "acme-corp".to_string(),
+                    "api".to_string(),
+                ),
+            ],
+            layout.parse_all(&path),
+        );
+    }
+
+    #[test]
+    fn round_trip_hyphenated_owner() {
+        let layout = Layout::from_str("{host}/{owner}@{repo}").unwrap();
+        let path = layout.render("github.com", "acme-corp", "api");
+
+        assert_eq!(PathBuf::from("github.com/acme-corp@api"), path);
+        assert_eq!(
+            Some((
+                "github.com".to_string(),
+                "acme-corp".to_string(),
+                "api".to_string(),
+            )),
+            layout.parse(&path),
+        );
+    }
+
+    #[test]
+    fn reject_invalid() {
+        assert!(Layout::from_str("{host}/{owner}").is_err());
+        assert!(Layout::from_str("{owner}{repo}").is_err());
+        assert!(Layout::from_str("{repo}/{repo}").is_err());
+        assert!(Layout::from_str("{host}//{repo}").is_err());
+        assert!(Layout::from_str("{unknown}/{repo}").is_err());
+    }
+}
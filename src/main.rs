@@ -1,15 +1,26 @@
 mod application;
+mod archive;
 mod cmd;
 mod config;
 mod console;
+mod format;
 mod git;
+mod history;
+mod index;
+mod layout;
+mod manifest;
 mod path;
 mod platform;
 mod profile;
 mod repository;
 mod root;
 mod rule;
+mod selector;
+mod store;
+mod subscription;
 mod sync;
+mod template;
+mod trash;
 mod url;
 
 use std::process::exit;
@@ -0,0 +1,464 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::{Error, Result, anyhow, bail};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Format of manifests used by other multi-repository tools.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Kind {
+    /// `.repos` YAML files of vcstool.
+    Vcstool,
+    /// XML manifests of Google's repo.
+    Repo,
+    /// `.mrconfig` files of myrepos.
+    Mr,
+}
+
+impl Kind {
+    pub fn parse(&self, s: &str) -> Result<Vec<Project>> {
+        match self {
+            Self::Vcstool => vcstool::parse(s),
+            Self::Repo => repo::parse(s),
+            Self::Mr => mr::parse(s),
+        }
+    }
+
+    pub fn dump(&self, projects: &[Project]) -> Result<String> {
+        match self {
+            Self::Vcstool => vcstool::dump(projects),
+            Self::Repo => repo::dump(projects),
+            Self::Mr => Ok(mr::dump(projects)),
+        }
+    }
+}
+
+impl FromStr for Kind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "vcstool" => Self::Vcstool,
+            "repo" => Self::Repo,
+            "mr" => Self::Mr,
+            _ => bail!("Unknown manifest format: {}", s),
+        })
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Vcstool => write!(f, "vcstool"),
+            Self::Repo => write!(f, "repo"),
+            Self::Mr => write!(f, "mr"),
+        }
+    }
+}
+
+/// A repository in a manifest.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Project {
+    /// Path of the checkout relative to the workspace.
+    pub path: String,
+    pub url: String,
+    /// Branch, tag or commit to check out.
+    pub revision: Option<String>,
+}
+
+mod vcstool {
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    struct File {
+        #[serde(default)]
+        repositories: BTreeMap<String, Repository>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Repository {
+        #[serde(rename = "type")]
+        kind: String,
+        url: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        version: Option<String>,
+    }
+
+    pub(super) fn parse(s: &str) -> Result<Vec<Project>> {
+        Ok(serde_yaml::from_str::<File>(s)?
+            .repositories
+            .into_iter()
+            .filter_map(|(path, r)| match r.kind.as_str() {
+                "git" => Some(Project {
+                    path,
+                    url: r.url,
+                    revision: r.version,
+                }),
+                _ => {
+                    warn!(
+                        "Skipped {}: {} repositories are not supported",
+                        path, r.kind
+                    );
+                    None
+                }
+            })
+            .collect())
+    }
+
+    pub(super) fn dump(projects: &[Project]) -> Result<String> {
+        Ok(serde_yaml::to_string(&File {
+            repositories: projects
+                .iter()
+                .map(|p| {
+                    (
+                        p.path.to_string(),
+                        Repository {
+                            kind: "git".to_string(),
+                            url: p.url.to_string(),
+                            version: p.revision.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        })?)
+    }
+}
+
+mod repo {
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(rename = "manifest")]
+    struct Manifest {
+        #[serde(rename = "remote", default)]
+        remotes: Vec<Remote>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default: Option<Defaults>,
+        #[serde(rename = "project", default)]
+        projects: Vec<Project>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Remote {
+        #[serde(rename = "@name")]
+        name: String,
+        #[serde(rename = "@fetch")]
+        fetch: String,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Defaults {
+        #[serde(rename = "@remote", skip_serializing_if = "Option::is_none")]
+        remote: Option<String>,
+        #[serde(rename = "@revision", skip_serializing_if = "Option::is_none")]
+        revision: Option<String>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Project {
+        #[serde(rename = "@name")]
+        name: String,
+        #[serde(rename = "@path", skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        #[serde(rename = "@remote", skip_serializing_if = "Option::is_none")]
+        remote: Option<String>,
+        #[serde(rename = "@revision", skip_serializing_if = "Option::is_none")]
+        revision: Option<String>,
+    }
+
+    pub(super) fn parse(s: &str) -> Result<Vec<super::Project>> {
+        let manifest = quick_xml::de::from_str::<Manifest>(s)?;
+        let default = manifest.default.unwrap_or(Defaults {
+            remote: None,
+            revision: None,
+        });
+
+        Ok(manifest
+            .projects
+            .into_iter()
+            .filter_map(|p| {
+                let path = p.path.unwrap_or_else(|| p.name.to_string());
+                let fetch = p
+                    .remote
+                    .as_ref()
+                    .or(default.remote.as_ref())
+                    .and_then(|name| manifest.remotes.iter().find(|r| &r.name == name))
+                    .map(|r| r.fetch.as_str());
+
+                match fetch {
+                    Some(f) if f.contains("://") || f.contains('@') => Some(super::Project {
+                        path,
+                        url: match f.ends_with(['/', ':']) {
+                            true => format!("{}{}", f, p.name),
+                            _ => format!("{}/{}", f, p.name),
+                        },
+                        revision: p
+                            .revision
+                            .or(default.revision.clone())
+                            .map(|r| r.trim_start_matches("refs/heads/").to_string()),
+                    }),
+                    Some(f) => {
+                        warn!(
+                            "Skipped {}: relative fetch URL is not supported: {}",
+                            path, f
+                        );
+                        None
+                    }
+                    _ => {
+                        warn!("Skipped {}: the remote could not be found", path);
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    pub(super) fn dump(projects: &[super::Project]) -> Result<String> {
+        let mut remotes = Vec::<Remote>::new();
+        let mut entries = Vec::new();
+        for p in projects {
+            // Splits the URL into the fetch base and the name of owner/repo.
+            let url = p.url.trim_end_matches(".git");
+            let (base, name) = url
+                .rsplitn(3, ['/', ':'])
+                .collect_tuple()
+                .map(|(repo, owner, base)| (base, format!("{}/{}", owner, repo)))
+                .ok_or_else(|| anyhow!("Could not split the URL: {}", p.url))?;
+
+            // Keeps the separator so that SSH URLs like git@github.com:owner/repo are restored.
+            let fetch = &p.url[..base.len() + 1];
+            let remote = match remotes.iter().find(|r| r.fetch == fetch) {
+                Some(r) => r.name.to_string(),
+                _ => {
+                    let name = format!("remote{}", remotes.len() + 1);
+                    remotes.push(Remote {
+                        name: name.to_string(),
+                        fetch: fetch.to_string(),
+                    });
+                    name
+                }
+            };
+
+            entries.push(Project {
+                name,
+                path: Some(p.path.to_string()),
+                remote: Some(remote),
+                revision: p.revision.clone(),
+            });
+        }
+
+        let mut xml = String::new();
+        let mut serializer = quick_xml::se::Serializer::new(&mut xml);
+        serializer.indent(' ', 2);
+        Manifest {
+            remotes,
+            default: None,
+            projects: entries,
+        }
+        .serialize(serializer)?;
+
+        Ok(format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}\n",
+            xml
+        ))
+    }
+}
+
+mod mr {
+    use super::*;
+
+    fn quote(s: &str) -> String {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+
+    /// Splits a command line into words, handling single and double quotes.
+    fn split_words(s: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut word: Option<String> = None;
+        let mut quote = None;
+        for c in s.chars() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), c) => word.get_or_insert_default().push(c),
+                (None, '\'' | '"') => {
+                    quote = Some(c);
+                    word.get_or_insert_default();
+                }
+                (None, c) if c.is_whitespace() => words.extend(word.take()),
+                (None, c) => word.get_or_insert_default().push(c),
+            }
+        }
+
+        words.extend(word);
+        words
+    }
+
+    /// Extracts the URL and branch from a `git clone` command line.
+    fn parse_checkout(command: &str) -> Option<(String, Option<String>)> {
+        let words = split_words(command);
+        let mut words = words.iter().skip_while(|w| w.as_str() != "clone").skip(1);
+
+        let mut branch = None;
+        while let Some(w) = words.next() {
+            match w.as_str() {
+                "-b" | "--branch" => branch = words.next().cloned(),
+                w if w.starts_with("--branch=") => {
+                    branch = Some(w.trim_start_matches("--branch=").to_string())
+                }
+                // Option values like `--depth 1` never contain a colon, unlike URLs.
+                w if w.starts_with('-') || !w.contains(':') => (),
+                w => return Some((w.to_string(), branch)),
+            }
+        }
+
+        None
+    }
+
+    pub(super) fn parse(s: &str) -> Result<Vec<Project>> {
+        let mut projects = Vec::new();
+        let mut section: Option<String> = None;
+        for line in s.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(name.trim().to_string());
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            let Some(path) = section.as_ref().filter(|s| s.as_str() != "DEFAULT") else {
+                continue;
+            };
+
+            if key.trim() != "checkout" {
+                continue;
+            }
+
+            match parse_checkout(value) {
+                Some((url, revision)) => projects.push(Project {
+                    path: path.to_string(),
+                    url,
+                    revision,
+                }),
+                _ => warn!("Skipped {}: could not find git clone in the checkout", path),
+            }
+        }
+
+        Ok(projects)
+    }
+
+    pub(super) fn dump(projects: &[Project]) -> String {
+        projects
+            .iter()
+            .map(|p| {
+                let name = p.path.rsplit('/').next().unwrap_or(&p.path);
+                let branch = p
+                    .revision
+                    .as_ref()
+                    .map(|r| format!("--branch {} ", quote(r)))
+                    .unwrap_or_default();
+
+                format!(
+                    "[{}]\ncheckout = git clone {}{} {}\n",
+                    p.path,
+                    branch,
+                    quote(&p.url),
+                    quote(name),
+                )
+            })
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projects() -> Vec<Project> {
+        vec![
+            Project {
+                path: "github.com/siketyan/ghr".to_string(),
+                url: "https://github.com/siketyan/ghr".to_string(),
+                revision: Some("main".to_string()),
+            },
+            Project {
+                path: "github.com/siketyan/dotfiles".to_string(),
+                url: "git@github.com:siketyan/dotfiles".to_string(),
+                revision: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        for kind in [Kind::Vcstool, Kind::Repo, Kind::Mr] {
+            let dumped = kind.dump(&projects()).unwrap();
+            let mut parsed = kind.parse(&dumped).unwrap();
+            parsed.sort_by(|a, b| b.path.cmp(&a.path));
+
+            assert_eq!(projects(), parsed, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn parse_repo_manifest() {
+        let manifest = r#"
+            <manifest>
+              <remote name="aosp" fetch="https://android.googlesource.com" />
+              <remote name="local" fetch=".." />
+              <default remote="aosp" revision="refs/heads/main" />
+              <project name="platform/build" path="build/make" />
+              <project name="device/common" remote="local" />
+              <project name="tools/repo" revision="v2.0" />
+            </manifest>
+        "#;
+
+        assert_eq!(
+            vec![
+                Project {
+                    path: "build/make".to_string(),
+                    url: "https://android.googlesource.com/platform/build".to_string(),
+                    revision: Some("main".to_string()),
+                },
+                Project {
+                    path: "tools/repo".to_string(),
+                    url: "https://android.googlesource.com/tools/repo".to_string(),
+                    revision: Some("v2.0".to_string()),
+                },
+            ],
+            Kind::Repo.parse(manifest).unwrap(),
+        );
+    }
+
+    #[test]
+    fn parse_mr_config() {
+        let config = r#"
+            [DEFAULT]
+            git_gc = git gc "$@"
+
+            [src/ghr]
+            checkout = git clone --depth 1 -b 'develop' 'https://github.com/siketyan/ghr.git' 'ghr'
+
+            [src/local]
+            update = git pull
+        "#;
+
+        assert_eq!(
+            vec![Project {
+                path: "src/ghr".to_string(),
+                url: "https://github.com/siketyan/ghr.git".to_string(),
+                revision: Some("develop".to_string()),
+            }],
+            Kind::Mr.parse(config).unwrap(),
+        );
+    }
+}
@@ -1,8 +1,23 @@
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
+use std::str::FromStr;
 
+use anyhow::{Result, anyhow};
+use dirs::home_dir;
+
+use crate::layout::Values;
 use crate::root::Root;
-use crate::url::Url;
+use crate::url::{Host, Url};
+
+/// Expands the leading `~/` of the path into the home directory.
+pub fn expand_home(path: &str) -> Result<PathBuf> {
+    Ok(match path.strip_prefix("~/") {
+        Some(p) => home_dir()
+            .ok_or_else(|| anyhow!("Could not find a home directory"))?
+            .join(p),
+        _ => PathBuf::from(path),
+    })
+}
 
 #[derive(Debug, Eq, PartialEq, Hash)]
 pub struct Path<'a> {
@@ -36,6 +51,22 @@ impl<'a> Path<'a> {
         }
     }
 
+    /// Composes the URL of the repository from its path, to match against the rules.
+    pub fn to_url(&self) -> Url {
+        let Ok(host) = Host::from_str(&self.host);
+
+        Url {
+            host,
+            owner: self.owner.clone(),
+            repo: self.repo.clone(),
+            ..Default::default()
+        }
+    }
+
+    pub fn root(&self) -> &'a Root {
+        self.root
+    }
+
     pub fn to_string_with(&self, host: bool, owner: bool) -> String {
         match (host, owner) {
             (false, true) => format!("{}/{}", self.owner, self.repo),
@@ -54,7 +85,9 @@ impl Display for Path<'_> {
 
 impl<'a> From<&Path<'a>> for PathBuf {
     fn from(p: &Path<'a>) -> Self {
-        p.root.path().join(&p.host).join(&p.owner).join(&p.repo)
+        p.root
+            .path()
+            .join(p.root.layout().render(&p.host, &p.owner, &p.repo))
     }
 }
 
@@ -73,21 +106,15 @@ pub struct PartialPath<'a> {
 
 impl<'a> From<&PartialPath<'a>> for PathBuf {
     fn from(p: &PartialPath<'a>) -> Self {
-        let mut path = p.root.path().to_owned();
-
-        match p.host.as_deref() {
-            Some(h) => path = path.join(h),
-            _ => return path,
-        }
-
-        match p.owner.as_deref() {
-            Some(o) => path = path.join(o),
-            _ => return path,
-        }
+        let relative = p.root.layout().render_partial(&Values {
+            host: p.host.clone(),
+            owner: p.owner.clone(),
+            repo: p.repo.clone(),
+        });
 
-        match p.repo.as_deref() {
-            Some(r) => path.join(r),
-            _ => path,
+        match relative.as_os_str().is_empty() {
+            true => p.root.path().to_owned(),
+            _ => p.root.path().join(relative),
         }
     }
 }
@@ -2,9 +2,10 @@ use anyhow::{Context, Result, anyhow, bail};
 use async_trait::async_trait;
 use gh_config::{GITHUB_COM, Hosts, is_enterprise, retrieve_token_from_env, retrieve_token_secure};
 use octocrab::Octocrab;
+use octocrab::models::Repository;
 use serde::Deserialize;
 
-use crate::platform::{Browse, Fork, Platform, PlatformInit};
+use crate::platform::{Browse, Fork, Locate, Platform, PlatformInit, Rename};
 use crate::url::Url;
 
 fn default_host() -> String {
@@ -71,6 +72,22 @@ impl PlatformInit for GitHub {
     }
 }
 
+impl GitHub {
+    fn to_url(url: &Url, repo: &Repository) -> Result<Url> {
+        Ok(Url {
+            owner: repo
+                .owner
+                .as_ref()
+                .ok_or_else(|| anyhow!("GitHub API did not return owner of the repository."))?
+                .login
+                .to_string(),
+            repo: repo.name.to_string(),
+            raw: None,
+            ..url.clone()
+        })
+    }
+}
+
 impl Platform for GitHub {}
 
 #[async_trait]
@@ -101,3 +118,28 @@ impl Browse for GitHub {
         ))?)
     }
 }
+
+#[async_trait]
+impl Locate for GitHub {
+    async fn locate(&self, url: &Url) -> Result<Url> {
+        // GitHub redirects requests to renamed or transferred repositories to their new location.
+        let repo = self.client.repos(&url.owner, &url.repo).get().await?;
+
+        Self::to_url(url, &repo)
+    }
+}
+
+#[async_trait]
+impl Rename for GitHub {
+    async fn rename(&self, url: &Url, name: &str) -> Result<Url> {
+        let repo: Repository = self
+            .client
+            .patch(
+                format!("/repos/{}/{}", url.owner, url.repo),
+                Some(&serde_json::json!({ "name": name })),
+            )
+            .await?;
+
+        Self::to_url(url, &repo)
+    }
+}
@@ -19,13 +19,24 @@ pub trait Browse {
     async fn get_browsable_url(&self, url: &Url) -> Result<url::Url>;
 }
 
+#[async_trait]
+pub trait Locate {
+    /// Finds the current URL of the repository, following renames and transfers.
+    async fn locate(&self, url: &Url) -> Result<Url>;
+}
+
+#[async_trait]
+pub trait Rename {
+    async fn rename(&self, url: &Url, name: &str) -> Result<Url>;
+}
+
 pub trait PlatformInit: Sized {
     type Config;
 
     fn init(config: &Self::Config) -> Result<Self>;
 }
 
-pub trait Platform: Fork + Browse {}
+pub trait Platform: Fork + Browse + Locate + Rename {}
 
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
@@ -1,10 +1,14 @@
-use std::collections::HashMap;
-use std::fmt::Formatter;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+#[cfg(unix)]
+use std::fs::{Permissions, set_permissions};
+use std::fs::{create_dir_all, read_to_string, remove_file, write};
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::result::Result as StdResult;
 
-use anyhow::Result;
-use git2::Repository;
+use anyhow::{Context, Result, anyhow, bail};
+use git2::{Config as GitConfig, ConfigLevel, Repository};
 use itertools::Itertools;
 use serde::de::{MapAccess, Visitor};
 use serde::ser::SerializeMap;
@@ -12,12 +16,126 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use toml::Table;
 use toml::value::Value;
 
+use crate::git::attributes::File as AttributesFile;
 use crate::git::exclude::{File, Node};
+use crate::path::expand_home;
 use crate::rule::ProfileRef;
 
-#[derive(Debug, Default)]
+const APPLIED_FILE_NAME: &str = "ghr-profile.toml";
+const HOOKS_PATH_KEY: &str = "core.hooksPath";
+
+/// Value of a Git config, written with the call matching its type.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    Bool(bool),
+    Integer(i64),
+    String(String),
+    /// Values of a multi-valued key such as `remote.origin.fetch`, replacing all existing ones.
+    Multi(Vec<String>),
+}
+
+impl ConfigValue {
+    fn from_toml(value: &Value) -> Option<Self> {
+        Some(match value {
+            Value::Boolean(b) => Self::Bool(*b),
+            Value::Integer(i) => Self::Integer(*i),
+            Value::String(s) => Self::String(s.clone()),
+            Value::Array(values) => Self::Multi(
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Value::String(s) => Some(s.clone()),
+                        Value::Boolean(_) | Value::Integer(_) => Some(v.to_string()),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+            _ => return None,
+        })
+    }
+
+    pub fn to_toml(&self) -> Value {
+        match self {
+            Self::Bool(b) => Value::Boolean(*b),
+            Self::Integer(i) => Value::Integer(*i),
+            Self::String(s) => Value::String(s.clone()),
+            Self::Multi(values) => Value::from(values.clone()),
+        }
+    }
+
+    /// Reads the current values of the key, as a string or multiple ones if set more than once.
+    fn read(config: &GitConfig, key: &str) -> Result<Option<Self>> {
+        let mut values = Vec::new();
+        let mut entries = config.multivar(key, None)?;
+        while let Some(entry) = entries.next() {
+            values.push(String::from_utf8_lossy(entry?.value_bytes()).to_string());
+        }
+
+        Ok(match values.len() {
+            0 => None,
+            1 => Some(Self::String(values.remove(0))),
+            _ => Some(Self::Multi(values)),
+        })
+    }
+
+    /// Checks if the key is set to this value, comparing booleans and integers as Git parses them.
+    fn matches(&self, config: &GitConfig, key: &str) -> Result<bool> {
+        Ok(match self {
+            Self::Bool(b) => config.get_bool(key).ok() == Some(*b),
+            Self::Integer(i) => config.get_i64(key).ok() == Some(*i),
+            _ => Self::read(config, key)?.is_some_and(|v| match (self, v) {
+                (Self::Multi(expected), Self::String(actual)) => expected == &vec![actual],
+                (_, actual) => self == &actual,
+            }),
+        })
+    }
+
+    fn write(&self, config: &mut GitConfig, key: &str) -> Result<()> {
+        // Git refuses to overwrite a multi-valued key with a single value.
+        if let Some(Self::Multi(_)) = Self::read(config, key)? {
+            remove_config(config, key)?;
+        }
+
+        match self {
+            Self::Bool(b) => config.set_bool(key, *b)?,
+            Self::Integer(i) => config.set_i64(key, *i)?,
+            Self::String(s) => config.set_str(key, s)?,
+            Self::Multi(values) => {
+                remove_config(config, key)?;
+                for value in values {
+                    // Appends a value, as the pattern never matches an existing one.
+                    config.set_multivar(key, "^$", value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ConfigValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Integer(i) => write!(f, "{}", i),
+            Self::String(s) => write!(f, "{}", s),
+            Self::Multi(values) => write!(f, "{}", values.join(",")),
+        }
+    }
+}
+
+/// Removes all values of the key, doing nothing if it is not set.
+fn remove_config(config: &mut GitConfig, key: &str) -> Result<()> {
+    match config.remove_multivar(key, ".*") {
+        Err(e) if e.code() != git2::ErrorCode::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct Configs {
-    map: HashMap<String, String>,
+    map: HashMap<String, ConfigValue>,
 }
 
 impl Configs {
@@ -38,20 +156,14 @@ impl Configs {
                 }
             }
 
-            inner.insert(
-                segments.last().unwrap().to_string(),
-                Value::String(value.to_string()),
-            );
+            inner.insert(segments.last().unwrap().to_string(), value.to_toml());
         }
 
         map
     }
 
-    fn extend_from_toml(&mut self, input: &Value, current_key: &str) {
+    fn extend_from_toml(&mut self, input: &Value, current_key: &str) -> StdResult<(), String> {
         match input {
-            Value::String(value) => {
-                self.map.insert(current_key.to_string(), value.clone());
-            }
             Value::Table(table) => {
                 for (key, value) in table {
                     self.extend_from_toml(
@@ -60,16 +172,27 @@ impl Configs {
                             true => key.clone(),
                             _ => format!("{}.{}", current_key, key),
                         },
-                    );
+                    )?;
                 }
             }
-            _ => (),
+            _ => {
+                let value = ConfigValue::from_toml(input).ok_or_else(|| {
+                    format!(
+                        "unsupported value for git config {}: {}",
+                        current_key, input
+                    )
+                })?;
+
+                self.map.insert(current_key.to_string(), value);
+            }
         }
+
+        Ok(())
     }
 }
 
 impl Deref for Configs {
-    type Target = HashMap<String, String>;
+    type Target = HashMap<String, ConfigValue>;
 
     fn deref(&self) -> &Self::Target {
         &self.map
@@ -117,57 +240,572 @@ impl<'de> Visitor<'de> for ConfigsVisitor {
         let mut configs = Configs::default();
 
         while let Some((key, value)) = map.next_entry::<String, Value>()? {
-            configs.extend_from_toml(&value, &key)
+            configs
+                .extend_from_toml(&value, &key)
+                .map_err(serde::de::Error::custom)?;
         }
 
         Ok(configs)
     }
 }
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+/// Script of a Git hook, written inline or read from a file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Hook {
+    Inline(String),
+    File { path: String },
+}
+
+impl Hook {
+    /// Reads the script. Paths must be absolute or start with `~/`, not to depend on the
+    /// directory ghr runs in.
+    fn script(&self) -> Result<String> {
+        Ok(match self {
+            Self::Inline(s) => s.clone(),
+            Self::File { path } => {
+                let expanded = expand_home(path)?;
+                if !expanded.is_absolute() {
+                    bail!(
+                        "Path of the hook script must be absolute or start with ~/: {}",
+                        path
+                    );
+                }
+
+                read_to_string(expanded)
+                    .with_context(|| format!("Could not read the hook script: {}", path))?
+            }
+        })
+    }
+}
+
+impl Display for Hook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Inline(s) => write!(f, "{}", s),
+            Self::File { path } => write!(f, "{}", path),
+        }
+    }
+}
+
+/// Finds the working directory of the repository, where `.git/info/exclude` is placed.
+fn workdir(repo: &Repository) -> Result<&std::path::Path> {
+    repo.workdir().ok_or_else(|| {
+        anyhow!(
+            "Bare repository is not supported: {}",
+            repo.path().to_string_lossy(),
+        )
+    })
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct Profile {
+    /// Names of the profiles to inherit from. Later ones take precedence over earlier ones.
+    /// Keys of ghr contain an underscore, which is not allowed in sections of Git configs.
+    #[serde(
+        default,
+        rename = "base_profiles",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub extends: Vec<String>,
     #[serde(default)]
     pub excludes: Vec<String>,
+    /// Git hooks to install into `.git/hooks`, keyed by their names such as `commit-msg`.
+    #[serde(
+        default,
+        rename = "git_hooks",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub hooks: BTreeMap<String, Hook>,
+    /// Entries to add to `.git/info/attributes` (not .gitattributes).
+    #[serde(
+        default,
+        rename = "git_attributes",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub attributes: Vec<String>,
+    /// Directory to set as `core.hooksPath`, to share hooks instead of installing them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks_path: Option<String>,
     #[serde(default, flatten)]
     pub configs: Configs,
 }
 
 impl Profile {
-    pub fn apply(&self, repo: &Repository) -> Result<()> {
-        let path = repo.workdir().unwrap();
+    /// Overrides configs with ones of the other profile, and adds its excludes not present yet.
+    fn overlay(&mut self, other: &Profile) {
+        self.configs.map.extend(other.configs.map.clone());
+        self.hooks.extend(other.hooks.clone());
+        for exclude in &other.excludes {
+            if !self.excludes.contains(exclude) {
+                self.excludes.push(exclude.clone());
+            }
+        }
+        for attribute in &other.attributes {
+            if !self.attributes.contains(attribute) {
+                self.attributes.push(attribute.clone());
+            }
+        }
+        if other.hooks_path.is_some() {
+            self.hooks_path = other.hooks_path.clone();
+        }
+    }
+
+    /// Git configs to write, including `core.hooksPath`.
+    fn git_configs(&self) -> Result<BTreeMap<String, ConfigValue>> {
+        let mut configs = self
+            .configs
+            .map
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<BTreeMap<_, _>>();
+
+        if let Some(path) = &self.hooks_path {
+            configs.insert(
+                HOOKS_PATH_KEY.to_string(),
+                ConfigValue::String(expand_home(path)?.to_string_lossy().to_string()),
+            );
+        }
+
+        Ok(configs)
+    }
+
+    fn scripts(&self) -> Result<BTreeMap<String, String>> {
+        self.hooks
+            .iter()
+            .map(|(name, hook)| Ok((name.clone(), hook.script()?)))
+            .collect()
+    }
+
+    /// Applies the profile to the repository, recording the values it wrote.
+    /// Values recorded by another profile are reverted first, and ones no longer in this profile
+    /// are reverted as well.
+    pub fn apply(&self, name: &str, repo: &Repository) -> Result<()> {
+        // Resolves everything that may fail before changing anything on disk.
+        let path = workdir(repo)?;
+        let scripts = self.scripts()?;
+        let configs = self.git_configs()?;
+
+        let mut applied = match Applied::load(repo)? {
+            Some(a) if a.profile == name => a,
+            Some(a) => {
+                a.revert(repo)?;
+                Applied::new(name)
+            }
+            _ => Applied::new(name),
+        };
+
         let mut exclude = File::load(path)?;
+        applied
+            .excludes
+            .retain(|v| match self.excludes.contains(v) {
+                true => true,
+                _ => {
+                    exclude.remove(&Node::Exclude(v.clone()));
+                    false
+                }
+            });
         for value in &self.excludes {
-            exclude.add_or_noop(Node::Exclude(value.to_string()));
+            let node = Node::Exclude(value.to_string());
+            if !exclude.contains(&node) {
+                exclude.add_or_noop(node);
+                if !applied.excludes.contains(value) {
+                    applied.excludes.push(value.clone());
+                }
+            }
+        }
+
+        exclude.save(path)?;
+
+        let mut attributes = AttributesFile::load(repo.path())?;
+        applied
+            .attributes
+            .retain(|v| match self.attributes.contains(v) {
+                true => true,
+                _ => {
+                    attributes.remove(v);
+                    false
+                }
+            });
+        for value in &self.attributes {
+            if !attributes.contains(value) {
+                attributes.add_or_noop(value);
+                if !applied.attributes.contains(value) {
+                    applied.attributes.push(value.clone());
+                }
+            }
+        }
+
+        attributes.save(repo.path())?;
+
+        let dir = repo.path().join("hooks");
+        for (name, record) in std::mem::take(&mut applied.hooks) {
+            match scripts.contains_key(&name) {
+                true => applied.hooks.insert(name, record),
+                _ => record.revert_hook(&dir.join(&name)).map(|_| None)?,
+            };
+        }
+        for (name, script) in scripts {
+            let path = dir.join(&name);
+            let previous = match applied.hooks.get(&name) {
+                Some(r) => r.previous.clone(),
+                _ => read_to_string(&path).ok(),
+            };
+
+            create_dir_all(&dir)?;
+            write(&path, &script)?;
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                set_permissions(&path, Permissions::from_mode(0o755))?;
+            }
+
+            applied.hooks.insert(
+                name,
+                AppliedValue {
+                    value: script,
+                    previous,
+                },
+            );
+        }
+
+        let mut config = repo.config()?.open_level(ConfigLevel::Local)?;
+        for (key, record) in std::mem::take(&mut applied.configs) {
+            match configs.contains_key(&key) {
+                true => applied.configs.insert(key, record),
+                _ => record.revert(&mut config, &key).map(|_| None)?,
+            };
+        }
+        for (key, value) in configs {
+            let previous = match applied.configs.get(&key) {
+                Some(r) => r.previous.clone(),
+                _ => ConfigValue::read(&config, &key)?,
+            };
+
+            value.write(&mut config, &key)?;
+            applied
+                .configs
+                .insert(key, AppliedConfig { value, previous });
+        }
+
+        applied.save(repo)
+    }
+
+    /// Compares the repository with the profile, including values recorded by the profile
+    /// applied previously that are no longer in this profile.
+    pub fn diff(&self, repo: &Repository) -> Result<Vec<Drift>> {
+        let applied = Applied::load(repo)?.unwrap_or_default();
+        let mut drifts = Vec::new();
+
+        let config = repo.config()?.open_level(ConfigLevel::Local)?;
+        let configs = self.git_configs()?;
+        for (key, expected) in &configs {
+            if !expected.matches(&config, key)? {
+                let actual = ConfigValue::read(&config, key)?.map(|v| v.to_toml());
+                drifts.push(Drift::new(key, actual, Some(expected.to_toml())));
+            }
+        }
+        for key in applied.configs.keys().filter(|k| !configs.contains_key(*k)) {
+            if let Some(actual) = ConfigValue::read(&config, key)? {
+                drifts.push(Drift::new(key, Some(actual.to_toml()), None));
+            }
+        }
+
+        let exclude = File::load(workdir(repo)?)?;
+        for value in &self.excludes {
+            if !exclude.contains(&Node::Exclude(value.clone())) {
+                drifts.push(Drift::new("excludes", None, Some(value.as_str().into())));
+            }
+        }
+        for value in applied
+            .excludes
+            .iter()
+            .filter(|v| !self.excludes.contains(v))
+        {
+            if exclude.contains(&Node::Exclude(value.clone())) {
+                drifts.push(Drift::new("excludes", Some(value.as_str().into()), None));
+            }
+        }
+
+        let attributes = AttributesFile::load(repo.path())?;
+        for value in &self.attributes {
+            if !attributes.contains(value) {
+                drifts.push(Drift::new(
+                    "git_attributes",
+                    None,
+                    Some(value.as_str().into()),
+                ));
+            }
+        }
+        for value in applied
+            .attributes
+            .iter()
+            .filter(|v| !self.attributes.contains(v))
+        {
+            if attributes.contains(value) {
+                drifts.push(Drift::new(
+                    "git_attributes",
+                    Some(value.as_str().into()),
+                    None,
+                ));
+            }
+        }
+
+        let dir = repo.path().join("hooks");
+        let scripts = self.scripts()?;
+        for (name, expected) in &scripts {
+            let actual = read_to_string(dir.join(name)).ok();
+            if actual.as_ref() != Some(expected) {
+                drifts.push(Drift::new(
+                    format!("git_hooks.{}", name),
+                    actual.map(Value::String),
+                    Some(expected.as_str().into()),
+                ));
+            }
+        }
+        for name in applied.hooks.keys().filter(|n| !scripts.contains_key(*n)) {
+            if let Ok(actual) = read_to_string(dir.join(name)) {
+                drifts.push(Drift::new(
+                    format!("git_hooks.{}", name),
+                    Some(Value::String(actual)),
+                    None,
+                ));
+            }
+        }
+
+        Ok(drifts)
+    }
+}
+
+/// Difference between a repository and a profile. `None` means the value is absent.
+#[derive(Debug, PartialEq)]
+pub struct Drift {
+    pub key: String,
+    pub actual: Option<Value>,
+    pub expected: Option<Value>,
+}
+
+impl Drift {
+    fn new(key: impl Into<String>, actual: Option<Value>, expected: Option<Value>) -> Self {
+        Self {
+            key: key.into(),
+            actual,
+            expected,
+        }
+    }
+}
+
+impl Display for Drift {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(actual) = &self.actual {
+            writeln!(f, "- {} = {}", self.key, actual)?;
+        }
+        if let Some(expected) = &self.expected {
+            writeln!(f, "+ {} = {}", self.key, expected)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A value written by a profile, with the one it replaced.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppliedValue {
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous: Option<String>,
+}
+
+impl AppliedValue {
+    /// Restores the hook, leaving it as it is if it was changed after installed.
+    fn revert_hook(&self, path: &std::path::Path) -> Result<()> {
+        if read_to_string(path).ok().as_ref() != Some(&self.value) {
+            return Ok(());
+        }
+
+        match &self.previous {
+            Some(p) => write(path, p)?,
+            _ => remove_file(path)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// A Git config written by a profile, with the one it replaced.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppliedConfig {
+    pub value: ConfigValue,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous: Option<ConfigValue>,
+}
+
+impl AppliedConfig {
+    /// Restores the config, leaving it as it is if it was changed after applied.
+    fn revert(&self, config: &mut GitConfig, key: &str) -> Result<()> {
+        if !self.value.matches(config, key)? {
+            return Ok(());
+        }
+
+        match &self.previous {
+            Some(p) => p.write(config, key)?,
+            _ => remove_config(config, key)?,
         }
 
+        Ok(())
+    }
+}
+
+/// Values written into a repository by applying a profile, stored in its Git directory
+/// to revert them or to detect drifts later.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Applied {
+    pub profile: String,
+    #[serde(default)]
+    pub configs: BTreeMap<String, AppliedConfig>,
+    #[serde(default)]
+    pub excludes: Vec<String>,
+    #[serde(default)]
+    pub attributes: Vec<String>,
+    #[serde(default)]
+    pub hooks: BTreeMap<String, AppliedValue>,
+}
+
+impl Applied {
+    fn new(profile: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn file_path(repo: &Repository) -> PathBuf {
+        repo.path().join(APPLIED_FILE_NAME)
+    }
+
+    /// Loads the record of the profile applied to the repository, if any.
+    pub fn load(repo: &Repository) -> Result<Option<Self>> {
+        let path = Self::file_path(repo);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(toml::from_str(&read_to_string(&path)?).with_context(
+            || format!("Could not read the applied profile: {}", path.display()),
+        )?))
+    }
+
+    fn save(&self, repo: &Repository) -> Result<()> {
+        write(Self::file_path(repo), toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Reverts the values written by the profile, then removes the record.
+    /// Values changed after applied are left as they are.
+    pub fn revert(self, repo: &Repository) -> Result<()> {
+        let mut config = repo.config()?.open_level(ConfigLevel::Local)?;
+        for (key, record) in &self.configs {
+            record.revert(&mut config, key)?;
+        }
+
+        let path = workdir(repo)?;
+        let mut exclude = File::load(path)?;
+        self.excludes
+            .iter()
+            .for_each(|e| exclude.remove(&Node::Exclude(e.clone())));
         exclude.save(path)?;
 
-        let mut config = repo.config()?;
-        for (key, value) in &self.configs.map {
-            config.set_str(key, value)?;
+        let mut attributes = AttributesFile::load(repo.path())?;
+        self.attributes.iter().for_each(|a| attributes.remove(a));
+        attributes.save(repo.path())?;
+
+        let dir = repo.path().join("hooks");
+        for (name, record) in &self.hooks {
+            record.revert_hook(&dir.join(name))?;
         }
 
+        remove_file(Self::file_path(repo))?;
         Ok(())
     }
 }
 
+/// Profiles as declared in the configuration, and resolved with the ones they extend.
+/// Dereferences into the resolved profiles.
 #[derive(Debug, Default, Deserialize)]
 pub struct Profiles {
     #[serde(flatten)]
     map: HashMap<String, Profile>,
+    #[serde(skip)]
+    resolved: HashMap<String, Profile>,
 }
 
 impl Profiles {
     pub fn resolve(&self, r: &ProfileRef) -> Option<(&str, &Profile)> {
         self.get_key_value(&r.name).map(|(s, p)| (s.as_str(), p))
     }
+
+    /// Finds the profile as declared, without merging the ones it extends.
+    pub fn declared(&self, name: &str) -> Option<&Profile> {
+        self.map.get(name)
+    }
+
+    /// Merges the profiles each profile extends into it, detecting unknown or circular ones.
+    pub fn resolve_extends(&mut self) -> Result<()> {
+        let mut resolved = HashMap::new();
+        for name in self.map.keys().sorted() {
+            self.resolve_one(name, &mut Vec::new(), &mut resolved)?;
+        }
+
+        self.resolved = resolved;
+        Ok(())
+    }
+
+    fn resolve_one(
+        &self,
+        name: &str,
+        stack: &mut Vec<String>,
+        resolved: &mut HashMap<String, Profile>,
+    ) -> Result<Profile> {
+        if let Some(p) = resolved.get(name) {
+            return Ok(p.clone());
+        }
+
+        if stack.iter().any(|s| s == name) {
+            bail!(
+                "Circular extends detected in the profiles: {} -> {}",
+                stack.join(" -> "),
+                name,
+            );
+        }
+
+        let declared = self.map.get(name).ok_or_else(|| match stack.last() {
+            Some(child) => anyhow!("Profile [{}] extends an unknown profile [{}]", child, name),
+            _ => anyhow!("Unknown profile: {}", name),
+        })?;
+
+        stack.push(name.to_string());
+
+        let mut profile = Profile::default();
+        for parent in &declared.extends {
+            profile.overlay(&self.resolve_one(parent, stack, resolved)?);
+        }
+
+        stack.pop();
+
+        profile.overlay(declared);
+        profile.extends = declared.extends.clone();
+        resolved.insert(name.to_string(), profile.clone());
+
+        Ok(profile)
+    }
 }
 
 impl Deref for Profiles {
     type Target = HashMap<String, Profile>;
 
     fn deref(&self) -> &Self::Target {
-        &self.map
+        &self.resolved
     }
 }
 
@@ -186,14 +824,168 @@ mod tests {
         let profile = toml::from_str::<Profile>(toml).unwrap();
         let configs = &profile.configs;
 
-        assert_eq!("User Taro", configs.get("user.name").unwrap().as_str());
+        assert_eq!("User Taro", configs.get("user.name").unwrap().to_string());
         assert_eq!(
             "taro@example.com",
-            configs.get("user.email").unwrap().as_str(),
+            configs.get("user.email").unwrap().to_string(),
         );
         assert_eq!(
             "ABCDEFGHIJKLMNOP",
-            configs.get("user.signingkey").unwrap().as_str(),
+            configs.get("user.signingkey").unwrap().to_string(),
+        );
+    }
+
+    #[test]
+    fn load_typed_git_configs() {
+        let toml = r#"
+        commit.gpgsign = true
+        core.abbrev = 12
+        remote.origin.fetch = ["+refs/heads/*:refs/remotes/origin/*", "+refs/pull/*:refs/remotes/origin/pr/*"]
+        "#;
+
+        let profile = toml::from_str::<Profile>(toml).unwrap();
+        let configs = &profile.configs;
+
+        assert_eq!(
+            &ConfigValue::Bool(true),
+            configs.get("commit.gpgsign").unwrap()
+        );
+        assert_eq!(
+            &ConfigValue::Integer(12),
+            configs.get("core.abbrev").unwrap()
+        );
+        assert_eq!(
+            &ConfigValue::Multi(vec![
+                "+refs/heads/*:refs/remotes/origin/*".to_string(),
+                "+refs/pull/*:refs/remotes/origin/pr/*".to_string(),
+            ]),
+            configs.get("remote.origin.fetch").unwrap(),
+        );
+
+        let serialized = toml::to_string(&profile).unwrap();
+        let reloaded = toml::from_str::<Profile>(&serialized).unwrap();
+        assert_eq!(profile.configs.map, reloaded.configs.map);
+
+        assert!(toml::from_str::<Profile>("core.abbrev = 1.5").is_err());
+    }
+
+    #[test]
+    fn resolve_extends() {
+        let mut profiles = toml::from_str::<Profiles>(
+            r#"
+            [base]
+            excludes = [".idea/"]
+            user.name = "User Taro"
+            user.email = "taro@example.com"
+
+            [signed]
+            commit.gpgsign = "true"
+
+            [work]
+            base_profiles = ["base", "signed"]
+            excludes = [".idea/", ".vscode/"]
+            user.email = "taro@work.example.com"
+            "#,
+        )
+        .unwrap();
+
+        profiles.resolve_extends().unwrap();
+
+        let work = profiles.get("work").unwrap();
+        assert_eq!(
+            "User Taro",
+            work.configs.get("user.name").unwrap().to_string()
+        );
+        assert_eq!(
+            "taro@work.example.com",
+            work.configs.get("user.email").unwrap().to_string(),
+        );
+        assert_eq!(
+            "true",
+            work.configs.get("commit.gpgsign").unwrap().to_string()
+        );
+        assert_eq!(vec![".idea/", ".vscode/"], work.excludes);
+
+        let declared = profiles.declared("work").unwrap();
+        assert!(declared.configs.get("user.name").is_none());
+    }
+
+    #[test]
+    fn load_hooks_and_attributes() {
+        let toml = r##"
+        git_attributes = ["*.png binary"]
+        hooks_path = "~/hooks"
+        git_hooks.commit-msg = "#!/bin/sh\nexit 0\n"
+        git_hooks.pre-push = { path = "~/hooks/pre-push" }
+        hooks.mailinglist = "dev@example.com"
+        user.name = "User Taro"
+        "##;
+
+        let profile = toml::from_str::<Profile>(toml).unwrap();
+
+        assert_eq!(vec!["*.png binary"], profile.attributes);
+        assert_eq!(Some("~/hooks"), profile.hooks_path.as_deref());
+        assert!(matches!(
+            profile.hooks.get("commit-msg"),
+            Some(Hook::Inline(s)) if s == "#!/bin/sh\nexit 0\n",
+        ));
+        assert!(matches!(
+            profile.hooks.get("pre-push"),
+            Some(Hook::File { path }) if path == "~/hooks/pre-push",
+        ));
+        assert_eq!(2, profile.hooks.len());
+        assert_eq!(
+            "dev@example.com",
+            profile
+                .configs
+                .get("hooks.mailinglist")
+                .unwrap()
+                .to_string(),
+        );
+        assert_eq!(2, profile.configs.len());
+    }
+
+    #[test]
+    fn detect_circular_extends() {
+        let mut profiles = toml::from_str::<Profiles>(
+            r#"
+            [a]
+            base_profiles = ["b"]
+
+            [b]
+            base_profiles = ["a"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            "Circular extends detected in the profiles: a -> b -> a",
+            profiles.resolve_extends().unwrap_err().to_string(),
+        );
+
+        let mut profiles = toml::from_str::<Profiles>(
+            r#"
+            [a]
+            base_profiles = ["unknown"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            "Profile [a] extends an unknown profile [unknown]",
+            profiles.resolve_extends().unwrap_err().to_string(),
+        );
+    }
+
+    #[test]
+    fn display_drift() {
+        assert_eq!(
+            "- user.name = \"Old\"\n+ user.name = \"New\"\n",
+            Drift::new("user.name", Some("Old".into()), Some("New".into())).to_string(),
+        );
+        assert_eq!(
+            "+ excludes = \".idea/\"\n",
+            Drift::new("excludes", None, Some(".idea/".into())).to_string(),
         );
     }
 }
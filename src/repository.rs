@@ -1,16 +1,118 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::{available_parallelism, scope};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, FixedOffset, TimeZone};
+use git2::{ErrorCode, Repository as GitRepository, StatusOptions};
 use itertools::Itertools;
+use tracing::{debug, warn};
 use walkdir::WalkDir;
 
+use crate::index::Index;
 use crate::path::Path;
-use crate::root::Root;
+use crate::root::{Root, Roots};
+use crate::rule::Rules;
+use crate::selector::Selector;
+use crate::url::{Patterns, Url};
+
+#[derive(Debug, Clone)]
+pub struct Commit {
+    pub id: String,
+    pub time: DateTime<FixedOffset>,
+}
+
+/// Metadata of a repository read from its working directory and Git database.
+#[derive(Debug, Clone)]
+pub struct Metadata {
+    pub branch: Option<String>,
+    pub head: Option<Commit>,
+    pub remotes: Vec<String>,
+    pub dirty: bool,
+    path: PathBuf,
+    size: OnceLock<u64>,
+}
+
+impl Metadata {
+    fn load<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let repo = GitRepository::open(path.as_ref())?;
+        let head = match repo.head() {
+            Ok(r) => Some(r),
+            Err(e) => match e.code() {
+                ErrorCode::UnbornBranch | ErrorCode::NotFound => None,
+                _ => return Err(e.into()),
+            },
+        };
+
+        let branch = head
+            .as_ref()
+            .filter(|h| h.is_branch())
+            .and_then(|h| h.shorthand().map(|s| s.to_string()));
+
+        let head = match head.map(|h| h.peel_to_commit()).transpose()? {
+            Some(c) => {
+                let time = c.time();
+                Some(Commit {
+                    id: c
+                        .as_object()
+                        .short_id()?
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    time: FixedOffset::east_opt(time.offset_minutes() * 60)
+                        .and_then(|o| o.timestamp_opt(time.seconds(), 0).single())
+                        .ok_or_else(|| anyhow!("Invalid commit time"))?,
+                })
+            }
+            _ => None,
+        };
+
+        let dirty = !repo
+            .statuses(Some(
+                StatusOptions::new()
+                    .include_untracked(true)
+                    .include_ignored(false)
+                    .recurse_untracked_dirs(false),
+            ))?
+            .is_empty();
+
+        Ok(Self {
+            branch,
+            head,
+            remotes: repo
+                .remotes()?
+                .iter()
+                .flatten()
+                .map(|r| r.to_string())
+                .collect(),
+            dirty,
+            path: PathBuf::from(path.as_ref()),
+            size: OnceLock::new(),
+        })
+    }
+
+    /// Sums sizes of the files on the first call, which walks the whole working directory.
+    pub fn size(&self) -> u64 {
+        *self.size.get_or_init(|| {
+            WalkDir::new(&self.path)
+                .into_iter()
+                .flatten()
+                .filter_map(|e| e.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum()
+        })
+    }
+}
 
 pub struct Repository {
-    #[allow(dead_code)]
     path: PathBuf,
+    metadata: OnceLock<Option<Metadata>>,
 }
 
 impl Repository {
@@ -20,8 +122,27 @@ impl Repository {
     {
         Self {
             path: PathBuf::from(path.as_ref()),
+            metadata: OnceLock::new(),
         }
     }
+
+    /// Reads the metadata on the first call. Returns `None` if the directory is not readable as a
+    /// Git repository.
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata
+            .get_or_init(|| match Metadata::load(&self.path) {
+                Ok(m) => Some(m),
+                Err(e) => {
+                    debug!(
+                        "Could not read metadata of {}: {}",
+                        self.path.to_string_lossy(),
+                        e,
+                    );
+                    None
+                }
+            })
+            .as_ref()
+    }
 }
 
 pub struct Repositories<'a> {
@@ -29,33 +150,164 @@ pub struct Repositories<'a> {
 }
 
 impl<'a> Repositories<'a> {
-    pub fn try_collect(root: &'a Root) -> Result<Self> {
+    /// Collects repositories across all the roots, using their indexes if available.
+    pub fn try_collect(roots: &'a Roots) -> Result<Self> {
+        let mut map = HashMap::new();
+        for root in roots.iter() {
+            match Index::load(root)? {
+                Some(index) => map.extend(index.entries().iter().map(|e| {
+                    let path = Path::new(root, &e.host, &e.owner, &e.repo);
+                    let repository = Repository::new(PathBuf::from(&path));
+
+                    (path, repository)
+                })),
+                _ => map.extend(Self::try_collect_in(root, &roots.others(root))?.map),
+            }
+        }
+
+        Ok(Self { map })
+    }
+
+    /// Collects repositories in the root, skipping the excluded directories.
+    pub fn try_collect_in(root: &'a Root, excludes: &[&std::path::Path]) -> Result<Self> {
+        let layout = root.layout();
+        // Used to parse ambiguous directories in the layout, such as `{owner}-{repo}`.
+        let index = Index::load(root)?;
+
         Ok(Self {
             map: WalkDir::new(root.path())
-                .min_depth(3)
-                .max_depth(3)
+                .max_depth(layout.depth())
                 .into_iter()
+                // Predicates of filter_entry are not applied to entries shallower than min_depth,
+                // so the depth is checked after filtering instead.
+                .filter_entry(|entry| {
+                    let hidden =
+                        entry.depth() == 1 && entry.file_name().to_string_lossy().starts_with('.');
+
+                    !hidden && !excludes.contains(&entry.path())
+                })
+                .filter_ok(|entry| entry.depth() == layout.depth())
                 .map_ok(|entry| entry.into_path())
                 .filter_ok(|path| path.is_dir())
                 .map_ok(|path| {
-                    let parts = path.strip_prefix(root.path())?.iter().collect::<Vec<_>>();
-
-                    Ok::<_, anyhow::Error>((
-                        Path::new(
-                            root,
-                            parts[0].to_string_lossy(),
-                            parts[1].to_string_lossy(),
-                            parts[2].to_string_lossy(),
-                        ),
-                        Repository::new(path),
-                    ))
+                    let candidates = layout.parse_all(path.strip_prefix(root.path())?);
+                    let parts = match candidates.len() {
+                        0 => {
+                            debug!(
+                                "Skipped a directory not matching the layout: {}",
+                                path.to_string_lossy(),
+                            );
+                            None
+                        }
+                        1 => candidates.into_iter().next(),
+                        _ => {
+                            let parts = disambiguate(candidates, index.as_ref(), &path);
+                            if parts.is_none() {
+                                warn!(
+                                    "Skipped a directory that cannot be parsed unambiguously in the layout: {}",
+                                    path.to_string_lossy(),
+                                );
+                            }
+
+                            parts
+                        }
+                    };
+
+                    Ok::<_, anyhow::Error>(parts.map(|(host, owner, repo)| {
+                        (Path::new(root, host, owner, repo), Repository::new(&path))
+                    }))
                 })
                 .flatten()
+                .flatten_ok()
                 .try_collect()?,
         })
     }
 }
 
+/// Picks one of the ways to parse an ambiguous directory, from the index of the root or the
+/// origin remote of the repository.
+fn disambiguate(
+    candidates: Vec<(String, String, String)>,
+    index: Option<&Index>,
+    path: &std::path::Path,
+) -> Option<(String, String, String)> {
+    if let Some(index) = index {
+        let found = candidates.iter().find(|(host, owner, repo)| {
+            index
+                .entries()
+                .iter()
+                .any(|e| &e.host == host && &e.owner == owner && &e.repo == repo)
+        });
+
+        if let Some(c) = found {
+            return Some(c.clone());
+        }
+    }
+
+    let url = GitRepository::open(path)
+        .ok()?
+        .find_remote("origin")
+        .ok()?
+        .url()
+        .and_then(|u| Url::from_str(u, &Patterns::default().with_defaults(), None).ok())?;
+
+    candidates.into_iter().find(|(_, owner, repo)| {
+        owner.eq_ignore_ascii_case(&url.owner) && repo.eq_ignore_ascii_case(&url.repo)
+    })
+}
+
+impl Repositories<'_> {
+    /// Keeps only the repositories matching the selector.
+    pub fn select(mut self, selector: &Selector, rules: &Rules) -> Self {
+        if selector.is_empty() {
+            return self;
+        }
+
+        if selector.needs_metadata() {
+            self.load_metadata();
+        }
+
+        self.map
+            .retain(|path, repo| selector.matches(path, repo, rules));
+        self
+    }
+
+    /// Reads metadata of all the repositories in parallel, to be used later without blocking.
+    pub fn load_metadata(&self) {
+        self.for_each_in_parallel(|r| {
+            r.metadata();
+        });
+    }
+
+    /// Reads metadata and sizes on disk of all the repositories in parallel.
+    pub fn load_sizes(&self) {
+        self.for_each_in_parallel(|r| {
+            if let Some(m) = r.metadata() {
+                m.size();
+            }
+        });
+    }
+
+    fn for_each_in_parallel<F>(&self, f: F)
+    where
+        F: Fn(&Repository) + Sync,
+    {
+        let repositories = self.map.values().collect::<Vec<_>>();
+        let next = AtomicUsize::new(0);
+        let workers = available_parallelism().map(|n| n.get()).unwrap_or(1);
+
+        scope(|s| {
+            for _ in 0..workers {
+                s.spawn(|| {
+                    while let Some(r) = repositories.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        f(r);
+                    }
+                });
+            }
+        });
+    }
+}
+
 impl<'a> IntoIterator for Repositories<'a> {
     type Item = (Path<'a>, Repository);
     type IntoIter = std::collections::hash_map::IntoIter<Path<'a>, Repository>;
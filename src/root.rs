@@ -1,17 +1,32 @@
+use std::collections::HashMap;
 use std::env::var;
+use std::fs::{read_dir, remove_dir};
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail};
 use dirs::home_dir;
+use itertools::Itertools;
+use serde::Deserialize;
 use tracing::debug;
 
+use crate::config::Config as GhrConfig;
+use crate::layout::Layout;
+use crate::path::expand_home;
+use crate::rule::Rules;
+use crate::url::Url;
+
 const ENV_VAR_KEY: &str = "GHR_ROOT";
 const DEFAULT_ROOT_NAME: &str = ".ghr";
+const DATA_DIR_NAME: &str = ".ghr";
+
+pub const DEFAULT_ROOT: &str = "default";
 
-#[derive(Debug, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Root {
+    name: String,
     path: PathBuf,
+    layout: Layout,
 }
 
 impl Root {
@@ -31,10 +46,143 @@ impl Root {
             path.to_str().unwrap_or_default(),
         );
 
-        Ok(Self { path })
+        Ok(Self {
+            name: DEFAULT_ROOT.to_string(),
+            path,
+            layout: Layout::default(),
+        })
+    }
+
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     pub fn path(&self) -> &PathBuf {
         &self.path
     }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    /// Directory to store data managed by ghr itself, such as subscriptions.
+    /// Hidden directories directly under the root are never treated as repositories.
+    pub fn data_path(&self) -> PathBuf {
+        self.path.join(DATA_DIR_NAME)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RootConfig {
+    pub path: String,
+    pub layout: Option<Layout>,
+}
+
+impl RootConfig {
+    fn expand_path(&self) -> Result<PathBuf> {
+        expand_home(&self.path)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    map: HashMap<String, RootConfig>,
+}
+
+/// All roots managed by ghr. The default root always comes first.
+#[derive(Debug)]
+pub struct Roots {
+    roots: Vec<Root>,
+}
+
+impl Roots {
+    pub fn find(config: &GhrConfig) -> Result<Self> {
+        let mut roots = vec![Root::find()?.with_layout(config.layout.clone())];
+
+        for (name, c) in config.roots.map.iter().sorted_by_key(|(name, _)| *name) {
+            if name == DEFAULT_ROOT {
+                bail!("The root name '{}' is reserved.", DEFAULT_ROOT);
+            }
+
+            roots.push(Root {
+                name: name.to_string(),
+                path: c.expand_path()?,
+                layout: c.layout.clone().unwrap_or_else(|| config.layout.clone()),
+            });
+        }
+
+        Ok(Self { roots })
+    }
+
+    pub fn default_root(&self) -> &Root {
+        &self.roots[0]
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Root> {
+        self.roots
+            .iter()
+            .find(|r| r.name == name)
+            .ok_or_else(|| anyhow!("Unknown root: {}", name))
+    }
+
+    /// Chooses the root where the repository should be placed, following the rules.
+    pub fn resolve(&self, url: &Url, rules: &Rules) -> Result<&Root> {
+        match rules.resolve_root(url) {
+            Some(name) => self.get(name),
+            _ => Ok(self.default_root()),
+        }
+    }
+
+    /// Paths of the roots other than the specified one, to skip roots nested in another root.
+    pub fn others(&self, root: &Root) -> Vec<&std::path::Path> {
+        self.roots
+            .iter()
+            .filter(|r| r != &root)
+            .map(|r| r.path().as_path())
+            .collect()
+    }
+
+    /// Removes the directories left empty above the path, such as `host/` and `host/owner/`,
+    /// up to the root containing it. Does nothing if the path is not in any root.
+    pub fn prune(&self, path: &std::path::Path) -> Result<()> {
+        let Some(root) = self
+            .roots
+            .iter()
+            .filter(|r| path.starts_with(r.path()))
+            .max_by_key(|r| r.path().components().count())
+        else {
+            return Ok(());
+        };
+
+        match path.parent() {
+            Some(parent) => prune_empty(parent, root.path()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Root> {
+        self.roots.iter()
+    }
+}
+
+/// Removes the directory and its ancestors while they are empty, stopping before `until`.
+pub fn prune_empty(dir: &std::path::Path, until: &std::path::Path) -> Result<()> {
+    let mut dir = Some(dir);
+    while let Some(d) = dir.filter(|d| d.starts_with(until) && *d != until) {
+        if !d.is_dir() || read_dir(d)?.next().is_some() {
+            break;
+        }
+
+        debug!("Removing the empty directory: {}", d.to_string_lossy());
+        remove_dir(d)?;
+        dir = d.parent();
+    }
+
+    Ok(())
 }
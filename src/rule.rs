@@ -8,10 +8,14 @@ pub struct ProfileRef {
 
 #[derive(Debug, Deserialize)]
 pub struct Rule {
-    pub profile: ProfileRef,
+    pub profile: Option<ProfileRef>,
+    pub root: Option<String>,
     pub host: Option<String>,
     pub owner: Option<String>,
     pub repo: Option<String>,
+    /// Protects the matching repositories from being deleted in bulk.
+    #[serde(default)]
+    pub protected: bool,
 }
 
 impl Rule {
@@ -33,7 +37,25 @@ impl Rule {
 pub struct Rules(Vec<Rule>);
 
 impl Rules {
+    /// Finds the first rule matching the URL that attaches a profile.
     pub fn resolve(&self, url: &Url) -> Option<&Rule> {
-        self.0.iter().find(|rule| rule.matches(url))
+        self.0
+            .iter()
+            .find(|rule| rule.profile.is_some() && rule.matches(url))
+    }
+
+    /// Finds the name of the root from the first rule matching the URL that chooses a root.
+    pub fn resolve_root(&self, url: &Url) -> Option<&str> {
+        self.0
+            .iter()
+            .filter(|rule| rule.matches(url))
+            .find_map(|rule| rule.root.as_deref())
+    }
+
+    /// Checks if any rule matching the URL protects the repository.
+    pub fn is_protected(&self, url: &Url) -> bool {
+        self.0
+            .iter()
+            .any(|rule| rule.protected && rule.matches(url))
     }
 }
@@ -0,0 +1,241 @@
+use std::str::FromStr;
+
+use anyhow::{Error, Result, anyhow, bail};
+use chrono::{TimeDelta, Utc};
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::path::Path;
+use crate::repository::Repository;
+use crate::rule::Rules;
+
+const KEYS: &[&str] = &[
+    "host", "owner", "name", "repo", "root", "profile", "dirty", "stale",
+];
+
+/// Pattern with `*` and `?` wildcards, matching the whole string.
+#[derive(Debug)]
+pub struct Glob(Regex);
+
+impl Glob {
+    pub fn matches(&self, s: &str) -> bool {
+        self.0.is_match(s)
+    }
+}
+
+impl FromStr for Glob {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let pattern = s
+            .chars()
+            .map(|c| match c {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                _ => regex::escape(&c.to_string()),
+            })
+            .join("");
+
+        Ok(Self(Regex::new(&format!("^{}$", pattern))?))
+    }
+}
+
+#[derive(Debug)]
+pub enum Term {
+    Host(Glob),
+    Owner(Glob),
+    Name(Glob),
+    Root(String),
+    Profile(String),
+    Dirty(bool),
+    Stale(TimeDelta),
+}
+
+impl Term {
+    fn key(&self) -> &'static str {
+        match self {
+            Self::Host(_) => "host",
+            Self::Owner(_) => "owner",
+            Self::Name(_) => "name",
+            Self::Root(_) => "root",
+            Self::Profile(_) => "profile",
+            Self::Dirty(_) => "dirty",
+            Self::Stale(_) => "stale",
+        }
+    }
+
+    fn matches(&self, path: &Path, repo: &Repository, rules: &Rules) -> bool {
+        match self {
+            Self::Host(g) => g.matches(&path.host),
+            Self::Owner(g) => g.matches(&path.owner),
+            Self::Name(g) => g.matches(&path.repo),
+            Self::Root(r) => path.root().name() == r,
+            Self::Profile(p) => rules
+                .resolve(&path.to_url())
+                .and_then(|r| r.profile.as_ref())
+                .map(|r| &r.name == p)
+                .unwrap_or_default(),
+            Self::Dirty(d) => repo.metadata().map(|m| m.dirty == *d).unwrap_or_default(),
+            Self::Stale(t) => repo
+                .metadata()
+                .and_then(|m| m.head.as_ref())
+                .map(|c| Utc::now().signed_duration_since(c.time) > *t)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl FromStr for Term {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (key, value) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Selector term must be in 'key:value' form: {}", s))?;
+
+        Ok(match key {
+            "host" => Self::Host(Glob::from_str(value)?),
+            "owner" => Self::Owner(Glob::from_str(value)?),
+            "name" | "repo" => Self::Name(Glob::from_str(value)?),
+            "root" => Self::Root(value.to_string()),
+            "profile" => Self::Profile(value.to_string()),
+            "dirty" => Self::Dirty(
+                value
+                    .parse()
+                    .map_err(|_| anyhow!("'dirty' must be true or false: {}", value))?,
+            ),
+            "stale" => Self::Stale(parse_duration(value)?),
+            _ => bail!(
+                "Unknown selector key: {} (expected one of {})",
+                key,
+                KEYS.join(", "),
+            ),
+        })
+    }
+}
+
+/// Filter of repositories, such as `owner:acme name:api-* dirty:true stale:90d`.
+/// Terms with different keys must all match, and terms with the same key match any of them.
+#[derive(Debug, Default)]
+pub struct Selector {
+    terms: Vec<Term>,
+}
+
+impl Selector {
+    pub fn parse<S>(terms: &[S]) -> Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        Ok(Self {
+            terms: terms
+                .iter()
+                .flat_map(|t| {
+                    t.as_ref()
+                        .split_whitespace()
+                        .map(|s| s.to_string())
+                        .collect_vec()
+                })
+                .map(|t| Term::from_str(&t))
+                .try_collect()?,
+        })
+    }
+
+    /// Splits a free-form query into a selector and the rest of the words.
+    /// Words are treated as selector terms only if they start with a known key.
+    pub fn split(query: &str) -> Result<(Self, String)> {
+        let (terms, rest): (Vec<_>, Vec<_>) = query.split_whitespace().partition(|w| {
+            w.split_once(':')
+                .map(|(k, _)| KEYS.contains(&k))
+                .unwrap_or_default()
+        });
+
+        Ok((Self::parse(&terms)?, rest.join(" ")))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether the selector reads metadata of repositories, which is expensive.
+    pub fn needs_metadata(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|t| matches!(t, Term::Dirty(_) | Term::Stale(_)))
+    }
+
+    pub fn matches(&self, path: &Path, repo: &Repository, rules: &Rules) -> bool {
+        self.terms
+            .iter()
+            .into_group_map_by(|t| t.key())
+            .values()
+            .all(|terms| terms.iter().any(|t| t.matches(path, repo, rules)))
+    }
+}
+
+/// Parses a duration such as `24h`, `90d`, `12w`, `6m` (30 days) or `1y` (365 days).
+pub fn parse_duration(s: &str) -> Result<TimeDelta> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| anyhow!("Duration must have a unit (h, d, w, m or y): {}", s))?;
+
+    let (value, unit) = s.split_at(split);
+    let value = value
+        .parse::<i64>()
+        .map_err(|_| anyhow!("Invalid duration: {}", s))?;
+
+    let hours = match unit {
+        "h" => 1,
+        "d" => 24,
+        "w" => 24 * 7,
+        "m" => 24 * 30,
+        "y" => 24 * 365,
+        _ => bail!("Unknown unit of duration: {}", s),
+    };
+
+    value
+        .checked_mul(hours)
+        .and_then(TimeDelta::try_hours)
+        .ok_or_else(|| anyhow!("Duration is too long: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_glob() {
+        let glob = Glob::from_str("api-*").unwrap();
+
+        assert!(glob.matches("api-server"));
+        assert!(glob.matches("api-"));
+        assert!(!glob.matches("my-api-server"));
+        assert!(Glob::from_str("a.?").unwrap().matches("a.b"));
+        assert!(!Glob::from_str("a.?").unwrap().matches("axb"));
+    }
+
+    #[test]
+    fn parse_durations() {
+        assert_eq!(TimeDelta::hours(24), parse_duration("24h").unwrap());
+        assert_eq!(TimeDelta::days(90), parse_duration("90d").unwrap());
+        assert_eq!(TimeDelta::days(14), parse_duration("2w").unwrap());
+        assert_eq!(TimeDelta::days(365), parse_duration("1y").unwrap());
+        assert!(parse_duration("90").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("3s").is_err());
+    }
+
+    #[test]
+    fn split_query() {
+        let (selector, rest) = Selector::split("api owner:acme github.com:foo").unwrap();
+
+        assert_eq!(1, selector.terms.len());
+        assert_eq!("api github.com:foo", rest);
+    }
+
+    #[test]
+    fn reject_invalid_terms() {
+        assert!(Selector::parse(&["owner"]).is_err());
+        assert!(Selector::parse(&["unknown:value"]).is_err());
+        assert!(Selector::parse(&["dirty:maybe"]).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use std::fs::{create_dir_all, rename, write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+// Serialises updates of the files stored under the roots within the process, such as parallel
+// cloning.
+pub static LOCK: Mutex<()> = Mutex::new(());
+
+/// Version of the files stored under the roots, such as the index and the history.
+#[derive(Deserialize, Serialize)]
+pub enum Version {
+    #[serde(rename = "1")]
+    V1,
+}
+
+/// Saves the value in TOML, creating the parent directory.
+/// Writes into a temporary file first, not to leave a broken file on failure.
+pub fn save<T>(path: &Path, value: &T) -> Result<()>
+where
+    T: Serialize,
+{
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    let temp = path.with_extension("toml.tmp");
+    write(&temp, toml::to_string(value)?)?;
+    rename(&temp, path)?;
+
+    Ok(())
+}
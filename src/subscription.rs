@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use itertools::Itertools;
+use walkdir::WalkDir;
+
+use crate::layout::Layout;
+use crate::root::Root;
+use crate::url::Url;
+
+const SUBSCRIPTIONS_DIR_NAME: &str = "subscriptions";
+
+pub const CONFIG_FILE_NAME: &str = "ghr.toml";
+
+/// A configuration repository cloned under the root, whose `ghr.toml` is merged into the config.
+#[derive(Debug)]
+pub struct Subscription {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Subscription {
+    pub fn resolve(root: &Root, url: &Url) -> Self {
+        let name = format!("{}:{}/{}", url.host, url.owner, url.repo);
+        let path = Self::dir(root).join(Layout::default().render(
+            &url.host.to_string(),
+            &url.owner,
+            &url.repo,
+        ));
+
+        Self { name, path }
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.path.join(CONFIG_FILE_NAME)
+    }
+
+    fn dir(root: &Root) -> PathBuf {
+        root.data_path().join(SUBSCRIPTIONS_DIR_NAME)
+    }
+}
+
+pub struct Subscriptions;
+
+impl Subscriptions {
+    /// Lists all subscriptions in the root, sorted by their names.
+    pub fn list(root: &Root) -> Result<Vec<Subscription>> {
+        let dir = Subscription::dir(root);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let layout = Layout::default();
+
+        Ok(WalkDir::new(&dir)
+            .min_depth(layout.depth())
+            .max_depth(layout.depth())
+            .into_iter()
+            .filter_ok(|entry| entry.file_type().is_dir())
+            .map_ok(|entry| {
+                let path = entry.into_path();
+                let name = path
+                    .strip_prefix(&dir)
+                    .ok()
+                    .and_then(|p| layout.parse(p))
+                    .map(|(host, owner, repo)| format!("{}:{}/{}", host, owner, repo))
+                    .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+                Subscription { name, path }
+            })
+            .try_collect::<_, Vec<_>, _>()?
+            .into_iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .collect())
+    }
+}
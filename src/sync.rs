@@ -5,7 +5,9 @@ use git2::{BranchType, ErrorCode, Reference, Repository as GitRepository};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
+use crate::manifest::Project;
 use crate::path::Path;
+use crate::url::{Patterns, Url};
 
 #[derive(Deserialize, Serialize)]
 pub struct BranchRef {
@@ -20,6 +22,9 @@ pub enum Ref {
     Remote(String),
     #[serde(rename = "branch")]
     Branch(BranchRef),
+    /// Branch, tag or commit imported from manifests of other tools.
+    #[serde(rename = "revision")]
+    Revision(String),
     // tag is not supported ... yet.
 }
 
@@ -140,6 +145,63 @@ pub struct File {
     pub repositories: Vec<Repository>,
 }
 
+impl File {
+    /// Converts the repositories into projects of manifests, preferring the origin remote.
+    pub fn to_projects(&self) -> Vec<Project> {
+        self.repositories
+            .iter()
+            .filter_map(|r| {
+                let remote = r
+                    .remotes
+                    .iter()
+                    .find(|remote| remote.name == "origin")
+                    .or(r.remotes.first())?;
+
+                Some(Project {
+                    path: format!("{}/{}/{}", r.host, r.owner, r.repo),
+                    url: remote.url.to_string(),
+                    revision: match &r.r#ref {
+                        Some(Ref::Branch(b)) => Some(b.name.to_string()),
+                        Some(Ref::Remote(name)) => name
+                            .strip_prefix("refs/remotes/")
+                            .and_then(|n| n.split_once('/'))
+                            .map(|(_, branch)| branch.to_string()),
+                        Some(Ref::Revision(r)) => Some(r.to_string()),
+                        _ => None,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Converts projects of manifests into repositories, skipping ones with an unsupported URL.
+    pub fn from_projects(projects: Vec<Project>, patterns: &Patterns) -> Self {
+        Self {
+            version: Version::V1,
+            repositories: projects
+                .into_iter()
+                .filter_map(|p| match Url::from_str(&p.url, patterns, None) {
+                    Ok(url) => Some(Repository {
+                        host: url.host.to_string(),
+                        owner: url.owner,
+                        repo: url.repo,
+                        r#ref: p.revision.map(Ref::Revision),
+                        remotes: vec![Remote {
+                            name: "origin".to_string(),
+                            url: p.url,
+                            push_url: None,
+                        }],
+                    }),
+                    Err(e) => {
+                        warn!("Skipped {}: {}", p.path, e);
+                        None
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
 impl<'a> FromIterator<Path<'a>> for File {
     fn from_iter<T>(iter: T) -> Self
     where
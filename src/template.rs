@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::env::temp_dir;
+use std::fs::{create_dir_all, read, remove_dir_all, set_permissions, write};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process::id;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow, bail};
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use crate::format::render_known;
+use crate::git::{CloneOptions, CloneRepository};
+use crate::path::expand_home;
+
+/// Temporary checkout of a template repository, removed on drop.
+struct Checkout(PathBuf);
+
+impl Checkout {
+    /// Prepares an empty directory unique to this run, clearing one left by a killed process.
+    fn new() -> Result<Self> {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir().join(format!("ghr-template-{}-{}", id(), nanos));
+        if path.exists() {
+            remove_dir_all(&path)?;
+        }
+
+        Ok(Self(path))
+    }
+}
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.0);
+    }
+}
+
+/// Files to start a new repository with, from a local directory or a Git repository.
+#[derive(Debug, Deserialize)]
+pub struct Template {
+    /// Path to the local directory.
+    pub path: Option<String>,
+    /// URL of the Git repository to clone.
+    pub url: Option<String>,
+    /// Branch of the Git repository to clone. Defaults to its default branch.
+    pub branch: Option<String>,
+}
+
+impl Template {
+    /// Renders files of the template into the directory.
+    /// Placeholders such as `{{repo}}` are substituted in both contents and names of the files,
+    /// and unknown ones such as `${{ github.ref }}` are left as they are.
+    pub fn render_into(
+        &self,
+        strategy: &impl CloneRepository,
+        dir: &Path,
+        fields: &[(String, String)],
+    ) -> Result<usize> {
+        let (source, _checkout) = match (&self.path, &self.url) {
+            (Some(path), None) => (expand_home(path)?, None),
+            (None, Some(url)) => {
+                let checkout = Checkout::new()?;
+                strategy.clone_repository(
+                    url,
+                    &checkout.0,
+                    &CloneOptions {
+                        branch: self.branch.clone(),
+                        single_branch: true,
+                        ..Default::default()
+                    },
+                )?;
+
+                (checkout.0.clone(), Some(checkout))
+            }
+            _ => bail!("A template must have either of path or url."),
+        };
+
+        if !source.is_dir() {
+            bail!(
+                "Could not find the template directory: {}",
+                source.to_string_lossy(),
+            );
+        }
+
+        let mut count = 0;
+        for entry in WalkDir::new(&source)
+            .min_depth(1)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.depth() != 1 || e.file_name() != ".git")
+        {
+            let entry = entry?;
+            let to = dir.join(render_known(
+                &entry.path().strip_prefix(&source)?.to_string_lossy(),
+                fields,
+            ));
+
+            if entry.file_type().is_dir() {
+                create_dir_all(&to)?;
+                continue;
+            }
+
+            // Binary files are copied as they are.
+            let content = read(entry.path())?;
+            match String::from_utf8(content) {
+                Ok(text) => write(&to, render_known(&text, fields))?,
+                Err(e) => write(&to, e.into_bytes())?,
+            }
+
+            set_permissions(&to, entry.path().metadata()?.permissions())?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Templates {
+    #[serde(flatten)]
+    map: HashMap<String, Template>,
+}
+
+impl Templates {
+    pub fn resolve(&self, name: &str) -> Result<&Template> {
+        self.get(name)
+            .ok_or_else(|| anyhow!("Unknown template: {}", name))
+    }
+}
+
+impl Deref for Templates {
+    type Target = HashMap<String, Template>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
@@ -0,0 +1,135 @@
+use std::fs::{create_dir_all, read_dir, remove_dir_all, rename};
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow, bail};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::index::Index;
+use crate::path::Path;
+use crate::root::{Root, Roots, prune_empty};
+
+const TRASH_DIR_NAME: &str = "trash";
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+fn trash_path(root: &Root) -> PathBuf {
+    root.data_path().join(TRASH_DIR_NAME)
+}
+
+/// Reads entries of the directory sorted by their names, or nothing if it does not exist.
+fn entries(dir: &std::path::Path) -> Result<Vec<(String, PathBuf)>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = read_dir(dir)?
+        .map(|e| {
+            let e = e?;
+            Ok((e.file_name().to_string_lossy().to_string(), e.path()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    entries.sort();
+    Ok(entries)
+}
+
+/// A repository moved into the trash of its root, as `trash/<timestamp>/<host>/<owner>/<repo>`.
+/// The trash is placed in the hidden data directory, so that trashed repositories are never listed.
+pub struct Trashed<'a> {
+    pub path: Path<'a>,
+    pub deleted_at: DateTime<Utc>,
+    location: PathBuf,
+}
+
+impl<'a> Trashed<'a> {
+    /// Moves the repository into the trash of its root.
+    pub fn put(path: &Path<'a>, deleted_at: DateTime<Utc>) -> Result<Self> {
+        let location = trash_path(path.root())
+            .join(deleted_at.format(TIMESTAMP_FORMAT).to_string())
+            .join(&path.host)
+            .join(&path.owner)
+            .join(&path.repo);
+
+        if location.exists() {
+            bail!(
+                "The repository is already in the trash: {}",
+                location.to_string_lossy(),
+            );
+        }
+
+        create_dir_all(
+            location
+                .parent()
+                .ok_or_else(|| anyhow!("Could not determine the parent directory"))?,
+        )?;
+        rename(PathBuf::from(path), &location)?;
+        Index::remove(path)?;
+
+        Ok(Self {
+            path: Path::new(path.root(), &path.host, &path.owner, &path.repo),
+            deleted_at,
+            location,
+        })
+    }
+
+    /// Lists the trashed repositories in all roots, the oldest first.
+    pub fn list(roots: &'a Roots) -> Result<Vec<Self>> {
+        let mut trashed = Vec::new();
+        for root in roots.iter() {
+            for (stamp, dir) in entries(&trash_path(root))? {
+                let Ok(deleted_at) = NaiveDateTime::parse_from_str(&stamp, TIMESTAMP_FORMAT) else {
+                    continue;
+                };
+
+                for (host, host_dir) in entries(&dir)? {
+                    for (owner, owner_dir) in entries(&host_dir)? {
+                        for (repo, location) in entries(&owner_dir)? {
+                            trashed.push(Self {
+                                path: Path::new(root, &host, &owner, &repo),
+                                deleted_at: deleted_at.and_utc(),
+                                location,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        trashed.sort_by_key(|t| t.deleted_at);
+        Ok(trashed)
+    }
+
+    /// Moves the repository back to its original location.
+    pub fn restore(self) -> Result<Path<'a>> {
+        let to = PathBuf::from(&self.path);
+        if to.exists() {
+            bail!(
+                "The original location is already taken: {}",
+                to.to_string_lossy(),
+            );
+        }
+
+        create_dir_all(
+            to.parent()
+                .ok_or_else(|| anyhow!("Could not determine the parent directory"))?,
+        )?;
+        rename(&self.location, &to)?;
+        self.prune()?;
+        Index::insert(&self.path)?;
+
+        Ok(self.path)
+    }
+
+    /// Deletes the repository permanently.
+    pub fn empty(self) -> Result<()> {
+        remove_dir_all(&self.location)?;
+        self.prune()
+    }
+
+    /// Removes the directories of the timestamp, host and owner left empty in the trash.
+    fn prune(&self) -> Result<()> {
+        match self.location.parent() {
+            Some(parent) => prune_empty(parent, &trash_path(self.path.root())),
+            _ => Ok(()),
+        }
+    }
+}